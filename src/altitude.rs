@@ -0,0 +1,153 @@
+use crate::{ConversionDelay, Interface, Measurement, Ms5611, Ms5611Error, OversampleRatio};
+
+/// Standard atmosphere pressure at sea level, in pascals.
+pub const STANDARD_SEA_LEVEL_PA: f32 = 101_325.0;
+
+/// Converts a pressure into an altitude above the given sea-level reference pressure using the
+/// international barometric formula. Accurate to within a few meters up to ~11 km, the top of the
+/// troposphere.
+pub fn altitude_m(pressure_pa: f32, sea_level_pa: f32) -> f32 {
+    44_330.0 * (1.0 - libm::powf(pressure_pa / sea_level_pa, 1.0 / 5.255))
+}
+
+/// Inverse of [`altitude_m`]: the sea-level pressure that makes `pressure_pa` read as
+/// `altitude_m` meters.
+pub fn sea_level_pressure_pa(pressure_pa: f32, altitude_m: f32) -> f32 {
+    pressure_pa / libm::powf(1.0 - altitude_m / 44_330.0, 5.255)
+}
+
+/// Vertical speed in m/s between two altitudes taken `dt_ms` milliseconds apart, positive when
+/// climbing. Returns 0 if `dt_ms` is 0.
+pub fn vertical_speed_mps(previous_altitude_m: f32, altitude_m: f32, dt_ms: u32) -> f32 {
+    if dt_ms == 0 {
+        return 0.0;
+    }
+
+    (altitude_m - previous_altitude_m) * 1000.0 / dt_ms as f32
+}
+
+/// Differentiates a series of altitudes into a vertical speed, smoothed with an exponential moving
+/// average since the raw derivative of a barometric altitude is very noisy.
+#[derive(Debug, Clone, Copy)]
+pub struct VerticalSpeed {
+    smoothing: f32,
+    last_altitude_m: Option<f32>,
+    speed_mps: f32,
+}
+
+impl VerticalSpeed {
+    /// `smoothing` is the weight of each new derivative sample, from 0 to 1: 1 disables smoothing,
+    /// smaller values smooth more at the cost of lag. Values outside the range are clamped.
+    pub fn new(smoothing: f32) -> Self {
+        VerticalSpeed {
+            smoothing: smoothing.clamp(0.0, 1.0),
+            last_altitude_m: None,
+            speed_mps: 0.0,
+        }
+    }
+
+    /// Feeds an altitude taken `dt_ms` milliseconds after the previous one and returns the
+    /// smoothed vertical speed in m/s. The first call only records the altitude and returns 0.
+    /// Samples with a `dt_ms` of 0 are ignored.
+    pub fn update(&mut self, altitude_m: f32, dt_ms: u32) -> f32 {
+        match self.last_altitude_m {
+            Some(last) if dt_ms > 0 => {
+                let speed = vertical_speed_mps(last, altitude_m, dt_ms);
+                self.speed_mps += (speed - self.speed_mps) * self.smoothing;
+                self.last_altitude_m = Some(altitude_m);
+            }
+            Some(_) => {}
+            None => self.last_altitude_m = Some(altitude_m),
+        }
+
+        self.speed_mps
+    }
+
+    /// Most recent smoothed vertical speed in m/s.
+    pub fn speed_mps(&self) -> f32 {
+        self.speed_mps
+    }
+
+    /// Forgets the previous altitude and speed, e.g. after a gap in the readings.
+    pub fn reset(&mut self) {
+        self.last_altitude_m = None;
+        self.speed_mps = 0.0;
+    }
+}
+
+/// The QNH passed to [`Ms5611::set_qnh_hpa`] is outside the plausible 900 to 1100 hPa range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidQnh(pub f32);
+
+impl Measurement {
+    /// Altitude in meters above the given sea-level reference pressure, see [`altitude_m`]. Pass
+    /// [`STANDARD_SEA_LEVEL_PA`] when no local reference is available.
+    pub fn altitude_m(&self, sea_level_pa: f32) -> f32 {
+        altitude_m(self.pressure_pa(), sea_level_pa)
+    }
+
+    /// Pressure altitude in meters, i.e. the altitude relative to the 1013.25 hPa standard
+    /// setting.
+    pub fn pressure_altitude_m(&self) -> f32 {
+        self.altitude_m(STANDARD_SEA_LEVEL_PA)
+    }
+
+    /// Density altitude in meters: the altitude in the ISA standard atmosphere at which air has
+    /// the same density as computed from this pressure and temperature.
+    ///
+    /// Assumes dry air (R = 287.05 J/(kg·K), sea-level density 1.225 kg/m³, lapse rate
+    /// 6.5 K/km) and that the sensor temperature matches the outside air, which self-heating or
+    /// an enclosure can easily violate. Only valid within the troposphere, below ~11 km.
+    pub fn density_altitude_m(&self) -> f32 {
+        let temperature_k = self.temperature_kelvin();
+        let density = self.pressure_pa() / (287.05 * temperature_k);
+
+        44_330.77 * (1.0 - libm::powf(density / 1.225, 0.234_969))
+    }
+}
+
+impl<I: Interface, DELAY> Ms5611<I, DELAY> {
+    /// Takes a measurement at a known elevation, such as the launch pad, and stores the equivalent
+    /// sea-level pressure as the reference for [`Ms5611::altitude_m`]. Returns the new reference in
+    /// pascals. Calibrating repeatedly against a stable pressure yields the same reference.
+    pub fn calibrate_sea_level<D: ConversionDelay>(
+        &mut self,
+        known_altitude_m: f32,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<f32, Ms5611Error<I::Error>> {
+        let measurement = self.read(osr, delay)?;
+        self.sea_level_pa = sea_level_pressure_pa(measurement.pressure_pa(), known_altitude_m);
+
+        Ok(self.sea_level_pa)
+    }
+
+    /// Sets the sea-level reference used by [`Ms5611::altitude_m`] from an altimeter setting
+    /// (QNH) in hectopascals, e.g. as reported by ATIS. Values outside 900 to 1100 hPa are
+    /// rejected as implausible and leave the current reference unchanged.
+    pub fn set_qnh_hpa(&mut self, qnh_hpa: f32) -> Result<(), InvalidQnh> {
+        if !(900.0..=1100.0).contains(&qnh_hpa) {
+            return Err(InvalidQnh(qnh_hpa));
+        }
+
+        self.sea_level_pa = qnh_hpa * 100.0;
+
+        Ok(())
+    }
+
+    /// Sea-level reference pressure in hectopascals, 1013.25 unless set or calibrated.
+    pub fn qnh_hpa(&self) -> f32 {
+        self.sea_level_pa / 100.0
+    }
+
+    /// Sea-level reference pressure in pascals, [`STANDARD_SEA_LEVEL_PA`] unless calibrated.
+    pub fn sea_level_pa(&self) -> f32 {
+        self.sea_level_pa
+    }
+
+    /// Altitude of a measurement in meters relative to the stored sea-level reference.
+    pub fn altitude_m(&self, measurement: &Measurement) -> f32 {
+        measurement.altitude_m(self.sea_level_pa)
+    }
+}
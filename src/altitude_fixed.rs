@@ -0,0 +1,90 @@
+use crate::Measurement;
+
+/// Standard atmosphere pressure at sea level, in mbar * 100.
+pub const STANDARD_SEA_LEVEL_MBAR_X100: u32 = 101_325;
+
+/// Altitude of the first entry in [`PRESSURE_TABLE`], in centimeters.
+const TABLE_START_CM: i64 = -50_000;
+/// Altitude step between entries in [`PRESSURE_TABLE`], in centimeters.
+const TABLE_STEP_CM: i64 = 25_000;
+
+/// Standard atmosphere pressure in mbar * 100 every 250 m from -500 m to 5000 m, from the same
+/// barometric formula as the `float` altitude helpers.
+const PRESSURE_TABLE: [u32; 23] = [
+    107_477, 104_364, 101_325, 98_358, 95_462, 92_635, 89_876, 87_184, 84_558, 81_997, 79_498,
+    77_062, 74_686, 72_370, 70_112, 67_912, 65_768, 63_680, 61_645, 59_663, 57_733, 55_854, 54_025,
+];
+
+/// Integer-only counterpart of the `float` feature's `altitude_m`, for targets without an FPU.
+/// Returns the altitude in centimeters above the given sea-level reference pressure.
+///
+/// Interpolates linearly in a table of the standard atmosphere, which stays within 1 m of the
+/// barometric formula between -500 m and 5000 m. Outside that range the nearest table segment is
+/// extrapolated and the error grows quickly, saturating at the `i32` limits for absurd inputs
+/// such as a sea-level reference of 0.
+pub fn altitude_cm(pressure_mbar_x100: u32, sea_level_mbar_x100: u32) -> i32 {
+    // The barometric formula only depends on p / p0, so rescale to the table's reference. The
+    // clamp keeps the extrapolation below from overflowing.
+    let pressure = ((pressure_mbar_x100 as i64 * STANDARD_SEA_LEVEL_MBAR_X100 as i64)
+        / (sea_level_mbar_x100.max(1) as i64))
+        .min(u32::MAX as i64);
+
+    let segment = PRESSURE_TABLE
+        .windows(2)
+        .position(|pair| pressure >= pair[1] as i64)
+        .unwrap_or(PRESSURE_TABLE.len() - 2);
+    let upper = PRESSURE_TABLE[segment] as i64;
+    let lower = PRESSURE_TABLE[segment + 1] as i64;
+
+    let altitude = TABLE_START_CM
+        + segment as i64 * TABLE_STEP_CM
+        + (upper - pressure) * TABLE_STEP_CM / (upper - lower);
+
+    altitude.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+impl Measurement {
+    /// Altitude in centimeters above the given sea-level reference pressure, see
+    /// [`altitude_cm`]. Pass [`STANDARD_SEA_LEVEL_MBAR_X100`] when no local reference is
+    /// available.
+    pub fn altitude_cm(&self, sea_level_mbar_x100: u32) -> i32 {
+        altitude_cm(self.pressure_mbar_x100, sea_level_mbar_x100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extreme_inputs_saturate() {
+        assert_eq!(altitude_cm(u32::MAX, 1), i32::MIN);
+        assert_eq!(
+            altitude_cm(u32::MAX, STANDARD_SEA_LEVEL_MBAR_X100),
+            i32::MIN
+        );
+        assert_eq!(altitude_cm(120_000, 10), i32::MIN);
+        assert!(altitude_cm(STANDARD_SEA_LEVEL_MBAR_X100, 0) < 0);
+        assert!(altitude_cm(0, STANDARD_SEA_LEVEL_MBAR_X100) > 0);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn matches_float_altitude() {
+        for sea_level_pa in [crate::STANDARD_SEA_LEVEL_PA, 98_000.0, 104_000.0] {
+            for altitude_m in (0..=4000).step_by(10) {
+                let pressure_pa =
+                    sea_level_pa * libm::powf(1.0 - altitude_m as f32 / 44_330.0, 5.255);
+                let pressure = libm::roundf(pressure_pa) as u32;
+
+                let expected_cm = crate::altitude_m(pressure as f32, sea_level_pa) * 100.0;
+                let actual_cm = altitude_cm(pressure, sea_level_pa as u32);
+
+                assert!(
+                    (actual_cm as f32 - expected_cm).abs() < 100.0,
+                    "{altitude_m} m: {actual_cm} cm vs {expected_cm} cm"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use core::fmt;
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::{I2c, Operation};
+
+use crate::interface::debug_assert_valid_address;
+use crate::{
+    adc_value, calibration_from_prom, compensate, conversion_delay_us, convert_command,
+    prom_words_from_bytes, Calibration, Command, ConvertChannel, Measurement, Ms5611Error,
+    OversampleRatio, MS5611_RESET_DELAY_MS,
+};
+
+/// Async counterpart of [`crate::Ms5611`] for `embedded-hal-async` I2C buses.
+pub struct Ms5611Async<I2C> {
+    address: u8,
+    i2c: I2C,
+    prom: Option<Calibration>,
+    /// Extra time added to every conversion wait, in percent of the datasheet maximum.
+    delay_margin_percent: u32,
+}
+
+impl<I2C> fmt::Debug for Ms5611Async<I2C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ms5611Async")
+            .field("address", &self.address)
+            .field("initialized", &self.prom.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I2C: I2c> Ms5611Async<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        debug_assert_valid_address(address);
+
+        Ms5611Async {
+            address,
+            i2c,
+            prom: None,
+            delay_margin_percent: 0,
+        }
+    }
+
+    /// Consumes the driver and returns the I2C bus handle.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    pub async fn reset(&mut self) -> Result<(), Ms5611Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &[Command::Reset.byte()])
+            .await
+            .map_err(Ms5611Error::Bus)
+    }
+
+    /// Sends the reset command and waits until the PROM can be read.
+    pub async fn reset_and_wait<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Ms5611Error<I2C::Error>> {
+        self.reset().await?;
+        delay.delay_ms(MS5611_RESET_DELAY_MS).await;
+
+        Ok(())
+    }
+
+    /// Resets the sensor, waits for the PROM to reload, then reads and verifies the PROM.
+    pub async fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Ms5611Error<I2C::Error>> {
+        self.reset_and_wait(delay).await?;
+        self.read_prom().await
+    }
+
+    /// Returns the calibration coefficients, or `None` if the PROM hasn't been read yet.
+    pub fn calibration(&self) -> Option<Calibration> {
+        self.prom
+    }
+
+    /// Loads previously stored calibration coefficients without touching the bus, so `read` can be
+    /// used immediately. This is the inverse of [`Ms5611Async::calibration`].
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.prom = Some(calibration);
+    }
+
+    /// Lengthens every conversion wait by `percent` of the datasheet maximum, like
+    /// [`crate::Ms5611::set_delay_margin_percent`]. Defaults to 0.
+    pub fn set_delay_margin_percent(&mut self, percent: u32) {
+        self.delay_margin_percent = percent;
+    }
+
+    pub async fn read_prom(&mut self) -> Result<(), Ms5611Error<I2C::Error>> {
+        let mut buf = [0u8; 16];
+
+        // Same single transaction as the blocking I2C interface, so the bus is held with repeated
+        // starts for the whole PROM read
+        let commands: [[u8; 1]; 8] =
+            core::array::from_fn(|index| [Command::PromRead(index as u8).byte()]);
+        let mut words = buf.chunks_exact_mut(2);
+        let mut operations: [Operation; 16] = core::array::from_fn(|index| {
+            if index % 2 == 0 {
+                Operation::Write(&commands[index / 2])
+            } else {
+                Operation::Read(words.next().unwrap())
+            }
+        });
+
+        self.i2c
+            .transaction(self.address, &mut operations)
+            .await
+            .map_err(Ms5611Error::Bus)?;
+
+        self.prom = Some(calibration_from_prom(&prom_words_from_bytes(&buf))?);
+
+        Ok(())
+    }
+
+    pub async fn read<D: DelayNs>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I2C::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let mut d1 = [0u8; 4];
+        let mut d2 = [0u8; 4];
+
+        self.i2c
+            .write(
+                self.address,
+                &[convert_command(ConvertChannel::Pressure, osr)],
+            )
+            .await
+            .map_err(Ms5611Error::Bus)?;
+        delay
+            .delay_us(conversion_delay_us(osr, self.delay_margin_percent))
+            .await;
+        // Same pipelining as the blocking driver: read back D1 and start the D2 conversion in one
+        // transaction
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    Operation::Write(&[Command::AdcRead.byte()]),
+                    Operation::Read(&mut d1[1..4]),
+                    Operation::Write(&[convert_command(ConvertChannel::Temperature, osr)]),
+                ],
+            )
+            .await
+            .map_err(Ms5611Error::Bus)?;
+        delay
+            .delay_us(conversion_delay_us(osr, self.delay_margin_percent))
+            .await;
+        self.i2c
+            .write_read(self.address, &[Command::AdcRead.byte()], &mut d2[1..4])
+            .await
+            .map_err(Ms5611Error::Bus)?;
+
+        Ok(compensate(&prom, adc_value(d1)?, adc_value(d2)?))
+    }
+}
+
+/// Stream of measurements taken at a fixed interval, created by [`Ms5611Async::measurements`].
+#[derive(Debug)]
+pub struct AsyncMeasurements<'a, I2C, D, P> {
+    ms5611: &'a mut Ms5611Async<I2C>,
+    osr: OversampleRatio,
+    conversion_delay: &'a mut D,
+    period_delay: &'a mut P,
+    period_ms: u32,
+    first: bool,
+}
+
+impl<I2C: I2c> Ms5611Async<I2C> {
+    /// Returns a stream that reads at `osr` every `period_ms` milliseconds.
+    ///
+    /// `conversion_delay` is only used to wait out the conversions and `period_delay` only for the
+    /// pause between samples, so they can be separate timers. The period is measured from the end
+    /// of one reading to the start of the next, so the actual sample interval is `period_ms` plus
+    /// the conversion time.
+    pub fn measurements<'a, D: DelayNs, P: DelayNs>(
+        &'a mut self,
+        osr: OversampleRatio,
+        conversion_delay: &'a mut D,
+        period_delay: &'a mut P,
+        period_ms: u32,
+    ) -> AsyncMeasurements<'a, I2C, D, P> {
+        AsyncMeasurements {
+            ms5611: self,
+            osr,
+            conversion_delay,
+            period_delay,
+            period_ms,
+            first: true,
+        }
+    }
+}
+
+impl<I2C: I2c, D: DelayNs, P: DelayNs> AsyncMeasurements<'_, I2C, D, P> {
+    /// Waits for the next sample period, then reads. The first call reads immediately.
+    pub async fn next(&mut self) -> Result<Measurement, Ms5611Error<I2C::Error>> {
+        if !self.first {
+            self.period_delay.delay_ms(self.period_ms).await;
+        }
+        self.first = false;
+
+        self.ms5611.read(self.osr, self.conversion_delay).await
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(feature = "spi")]
+use embedded_hal::digital::OutputPin;
+#[cfg(feature = "i2c")]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "spi")]
+use embedded_hal::spi::SpiBus;
+
+#[cfg(feature = "i2c")]
+use crate::I2cInterface;
+#[cfg(feature = "spi")]
+use crate::SpiInterface;
+use crate::{Interface, Ms5611, Ms5611Address, OversampleRatio, Variant};
+
+/// Configures the address, oversample ratio, variant and delay provider of an [`Ms5611`] up
+/// front, so that [`Ms5611::read_configured`] can be called without arguments.
+pub struct Ms5611Builder<DELAY = ()> {
+    #[cfg_attr(not(feature = "i2c"), allow(dead_code))]
+    address: u8,
+    osr: OversampleRatio,
+    variant: Variant,
+    power_on_delay_ms: u32,
+    delay: DELAY,
+}
+
+impl Ms5611Builder {
+    /// Starts from address 0x77 (CSB low), OSR 4096 and the MS5611 variant, without a delay.
+    pub fn new() -> Self {
+        Ms5611Builder {
+            address: Ms5611Address::Csb0 as u8,
+            osr: OversampleRatio::default(),
+            variant: Variant::Ms5611,
+            power_on_delay_ms: 0,
+            delay: (),
+        }
+    }
+}
+
+impl Default for Ms5611Builder {
+    fn default() -> Self {
+        Ms5611Builder::new()
+    }
+}
+
+impl<DELAY> Ms5611Builder<DELAY> {
+    /// I2C address of the sensor, ignored when building an SPI driver.
+    pub fn address(mut self, address: impl Into<u8>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    pub fn oversample_ratio(mut self, osr: OversampleRatio) -> Self {
+        self.osr = osr;
+        self
+    }
+
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Time [`Ms5611::init`] waits before resetting the sensor, see
+    /// [`Ms5611::set_power_on_delay_ms`].
+    pub fn power_on_delay_ms(mut self, ms: u32) -> Self {
+        self.power_on_delay_ms = ms;
+        self
+    }
+
+    /// Delay provider stored in the driver and used for conversions by
+    /// [`Ms5611::read_configured`] and [`Ms5611::read_with`].
+    pub fn delay<D>(self, delay: D) -> Ms5611Builder<D> {
+        Ms5611Builder {
+            address: self.address,
+            osr: self.osr,
+            variant: self.variant,
+            power_on_delay_ms: self.power_on_delay_ms,
+            delay,
+        }
+    }
+
+    #[cfg(feature = "i2c")]
+    pub fn build_i2c<I2C: I2c>(self, i2c: I2C) -> Ms5611<I2cInterface<I2C>, DELAY> {
+        let interface = I2cInterface::new(i2c, self.address);
+        self.build(interface)
+    }
+
+    #[cfg(feature = "spi")]
+    pub fn build_spi<SPI: SpiBus, CS: OutputPin>(
+        self,
+        spi: SPI,
+        cs: CS,
+    ) -> Ms5611<SpiInterface<SPI, CS>, DELAY> {
+        self.build(SpiInterface::new(spi, cs))
+    }
+
+    fn build<I: Interface>(self, interface: I) -> Ms5611<I, DELAY> {
+        let mut ms5611 = Ms5611::with_config(interface, self.osr, self.delay);
+        ms5611.set_variant(self.variant);
+        ms5611.set_power_on_delay_ms(self.power_on_delay_ms);
+        ms5611
+    }
+}
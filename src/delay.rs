@@ -0,0 +1,61 @@
+use embedded_hal::delay::DelayNs;
+
+/// Waits for resets and ADC conversions to complete.
+///
+/// Implemented for every embedded-hal [`DelayNs`]. Implement it directly to plug in something
+/// else, e.g. an RTOS sleep that yields to the scheduler instead of busy-waiting.
+pub trait ConversionDelay {
+    fn delay_ms(&mut self, ms: u32);
+
+    /// Microsecond delay used for conversions. Defaults to rounding up to whole milliseconds,
+    /// override it if the platform can wait more precisely.
+    fn delay_us(&mut self, us: u32) {
+        self.delay_ms(us.div_ceil(1000));
+    }
+}
+
+impl<T: DelayNs> ConversionDelay for T {
+    fn delay_ms(&mut self, ms: u32) {
+        DelayNs::delay_ms(self, ms);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        DelayNs::delay_us(self, us);
+    }
+}
+
+/// Adapts a plain millisecond delay function into a [`ConversionDelay`].
+///
+/// Function pointers have a single concrete type, so storing one with
+/// [`crate::Ms5611Builder::delay`] gives an `Ms5611<_, FnDelay>` that is easy to name in struct
+/// fields and state machines, unlike a closure or HAL delay type.
+#[derive(Debug, Clone, Copy)]
+pub struct FnDelay(pub fn(u32));
+
+impl ConversionDelay for FnDelay {
+    fn delay_ms(&mut self, ms: u32) {
+        (self.0)(ms);
+    }
+}
+
+/// Where the driver's read path waits out a conversion: a delay passed in by the caller, or
+/// [`StoredDelay`] for the one stored in the driver. `stored` is borrowed separately from the rest
+/// of the driver, so it can be used while the read path holds `&mut self`.
+pub(crate) trait Wait<DELAY> {
+    fn wait_us(&mut self, us: u32, stored: &mut DELAY);
+}
+
+impl<D: ConversionDelay, DELAY> Wait<DELAY> for D {
+    fn wait_us(&mut self, us: u32, _stored: &mut DELAY) {
+        self.delay_us(us);
+    }
+}
+
+/// Waits with the delay provider stored through [`crate::Ms5611Builder::delay`].
+pub(crate) struct StoredDelay;
+
+impl<DELAY: ConversionDelay> Wait<DELAY> for StoredDelay {
+    fn wait_us(&mut self, us: u32, stored: &mut DELAY) {
+        stored.delay_us(us);
+    }
+}
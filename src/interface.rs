@@ -0,0 +1,245 @@
+#[cfg(feature = "spi")]
+use embedded_hal::digital::OutputPin;
+#[cfg(feature = "i2c")]
+use embedded_hal::i2c::{Error as _, ErrorKind, I2c, Operation};
+#[cfg(feature = "spi")]
+use embedded_hal::spi::SpiBus;
+
+use core::fmt;
+
+use crate::Command;
+
+/// Byte transport used to talk to the MS5611.
+///
+/// The sensor speaks the same command set over I2C and SPI, so the driver only needs to be able
+/// to send a command byte and optionally clock back a response.
+pub trait Interface {
+    type Error;
+
+    /// Sends a single command byte.
+    fn write_command(&mut self, command: u8) -> Result<(), Self::Error>;
+
+    /// Sends a command byte and reads the response into `buf`.
+    ///
+    /// Implementations must not release the bus between the command and the response, so another
+    /// master on a shared bus can't slip in and corrupt it. On I2C this means a repeated start.
+    fn command_read(&mut self, command: u8, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Sends `command`, reads the response into `buf`, then sends `next_command`.
+    ///
+    /// Used to read back one ADC conversion and immediately start the next. The default
+    /// implementation is a [`Interface::command_read`] followed by a [`Interface::write_command`].
+    /// Transports that can chain them into a single bus transaction should override it.
+    fn command_read_then_command(
+        &mut self,
+        command: u8,
+        buf: &mut [u8],
+        next_command: u8,
+    ) -> Result<(), Self::Error> {
+        self.command_read(command, buf)?;
+        self.write_command(next_command)
+    }
+
+    /// Reads all eight PROM words into `buf`, big-endian, word 0 first.
+    ///
+    /// The default implementation issues one [`Interface::command_read`] per word. Transports that
+    /// can chain the reads into a single bus transaction should override it.
+    fn read_prom(&mut self, buf: &mut [u8; 16]) -> Result<(), Self::Error> {
+        for (index, word) in (0u8..).zip(buf.chunks_exact_mut(2)) {
+            self.command_read(Command::PromRead(index).byte(), word)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if `error` means no device answered at all, rather than a device answering
+    /// badly. [`crate::Ms5611::is_connected`] reports such errors as `Ok(false)`.
+    ///
+    /// The default implementation returns false, as transports like SPI have no way to tell.
+    fn is_absent_device_error(_error: &Self::Error) -> bool {
+        false
+    }
+}
+
+/// I2C address of the sensor, selected by the level of the CSB pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ms5611Address {
+    /// CSB pulled low.
+    Csb0 = 0x77,
+    /// CSB pulled high.
+    Csb1 = 0x76,
+}
+
+impl From<Ms5611Address> for u8 {
+    fn from(address: Ms5611Address) -> Self {
+        address as u8
+    }
+}
+
+#[cfg(any(feature = "i2c", feature = "async"))]
+pub(crate) fn debug_assert_valid_address(address: u8) {
+    debug_assert!(
+        address == Ms5611Address::Csb0 as u8 || address == Ms5611Address::Csb1 as u8,
+        "MS5611 I2C address must be 0x76 or 0x77"
+    );
+}
+
+#[cfg(feature = "i2c")]
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+#[cfg(feature = "i2c")]
+impl<I2C> I2cInterface<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        debug_assert_valid_address(address);
+
+        I2cInterface { i2c, address }
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Returns the I2C bus handle.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    pub fn set_address(&mut self, address: u8) {
+        debug_assert_valid_address(address);
+
+        self.address = address;
+    }
+}
+
+// Hand-written so the bus handle doesn't need to implement Debug
+#[cfg(feature = "i2c")]
+impl<I2C> fmt::Debug for I2cInterface<I2C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I2cInterface")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl<I2C: I2c> Interface for I2cInterface<I2C> {
+    type Error = I2C::Error;
+
+    fn write_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.i2c.write(self.address, &[command])
+    }
+
+    fn command_read(&mut self, command: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c.write_read(self.address, &[command], buf)
+    }
+
+    fn command_read_then_command(
+        &mut self,
+        command: u8,
+        buf: &mut [u8],
+        next_command: u8,
+    ) -> Result<(), Self::Error> {
+        // The next command follows after a repeated start, saving a stop and start condition
+        self.i2c.transaction(
+            self.address,
+            &mut [
+                Operation::Write(&[command]),
+                Operation::Read(buf),
+                Operation::Write(&[next_command]),
+            ],
+        )
+    }
+
+    fn read_prom(&mut self, buf: &mut [u8; 16]) -> Result<(), Self::Error> {
+        // Each word still needs its own command, but issuing them as one transaction separates
+        // them with repeated starts instead of releasing the bus eight times
+        let commands: [[u8; 1]; 8] =
+            core::array::from_fn(|index| [Command::PromRead(index as u8).byte()]);
+        let mut words = buf.chunks_exact_mut(2);
+        let mut operations: [Operation; 16] = core::array::from_fn(|index| {
+            if index % 2 == 0 {
+                Operation::Write(&commands[index / 2])
+            } else {
+                Operation::Read(words.next().unwrap())
+            }
+        });
+
+        self.i2c.transaction(self.address, &mut operations)
+    }
+
+    fn is_absent_device_error(error: &Self::Error) -> bool {
+        matches!(error.kind(), ErrorKind::NoAcknowledge(_))
+    }
+}
+
+#[cfg(feature = "spi")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiError<SPI, CS> {
+    /// Error from the SPI bus.
+    Spi(SPI),
+    /// Error from the chip-select pin.
+    Cs(CS),
+}
+
+/// SPI transport with a dedicated chip-select pin. The PS pin of the sensor must be tied low.
+#[cfg(feature = "spi")]
+pub struct SpiInterface<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+#[cfg(feature = "spi")]
+impl<SPI, CS> SpiInterface<SPI, CS> {
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        SpiInterface { spi, cs }
+    }
+
+    /// Returns the SPI bus and chip-select pin.
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<SPI, CS> fmt::Debug for SpiInterface<SPI, CS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpiInterface").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<SPI: SpiBus, CS: OutputPin> SpiInterface<SPI, CS> {
+    fn transaction<F>(&mut self, f: F) -> Result<(), SpiError<SPI::Error, CS::Error>>
+    where
+        F: FnOnce(&mut SPI) -> Result<(), SPI::Error>,
+    {
+        self.cs.set_low().map_err(SpiError::Cs)?;
+        let result = f(&mut self.spi)
+            .and_then(|()| self.spi.flush())
+            .map_err(SpiError::Spi);
+        // Always release chip select, even if the transfer failed
+        self.cs.set_high().map_err(SpiError::Cs)?;
+
+        result
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<SPI: SpiBus, CS: OutputPin> Interface for SpiInterface<SPI, CS> {
+    type Error = SpiError<SPI::Error, CS::Error>;
+
+    fn write_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.transaction(|spi| spi.write(&[command]))
+    }
+
+    fn command_read(&mut self, command: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.transaction(|spi| {
+            spi.write(&[command])?;
+            spi.read(buf)
+        })
+    }
+}
@@ -1,21 +1,81 @@
 #![deny(unsafe_code)]
 #![cfg_attr(not(test), no_std)]
 
-const MS5611_RESET: u8 = 0b0001_1110;
-const MS5611_PROM_READ: u8 = 0b1010_0000;
-const MS5611_READ_ADC: u8 = 0b0000_0000;
+#[cfg(not(any(feature = "i2c", feature = "spi")))]
+compile_error!("at least one of the `i2c` and `spi` features must be enabled");
 
-#[derive(Debug, Clone, Copy)]
+mod interface;
+
+pub use interface::{Interface, Ms5611Address};
+
+#[cfg(feature = "i2c")]
+pub use interface::I2cInterface;
+
+#[cfg(feature = "spi")]
+pub use interface::{SpiError, SpiInterface};
+
+mod altitude_fixed;
+mod builder;
+mod delay;
+mod redundant;
+mod relative_altitude;
+mod stream;
+
+pub use altitude_fixed::{altitude_cm, STANDARD_SEA_LEVEL_MBAR_X100};
+pub use builder::Ms5611Builder;
+pub use delay::{ConversionDelay, FnDelay};
+pub use redundant::{combine_redundant, RedundantMeasurement};
+pub use relative_altitude::relative_altitude_m;
+pub use stream::Measurements;
+
+#[cfg(feature = "float")]
+mod altitude;
+
+#[cfg(feature = "float")]
+pub use altitude::{
+    altitude_m, sea_level_pressure_pa, vertical_speed_mps, InvalidQnh, VerticalSpeed,
+    STANDARD_SEA_LEVEL_PA,
+};
+
+#[cfg(feature = "async")]
+mod asynch;
+
+#[cfg(feature = "async")]
+pub use asynch::{AsyncMeasurements, Ms5611Async};
+
+/// Set in a conversion command to convert temperature (D2) instead of pressure (D1).
+const MS5611_CONVERT_D2: u8 = 0b0001_0000;
+
+/// Time the PROM needs to reload after a reset, rounded up from the datasheet's 2.8 ms.
+const MS5611_RESET_DELAY_MS: u32 = 3;
+
+/// ADC oversample ratio. Higher ratios trade conversion time for lower noise, and the variants
+/// order by resolution, so `osr >= OversampleRatio::Osr1024` means "at least OSR 1024".
+///
+/// Defaults to OSR 4096, the highest resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum OversampleRatio {
     Osr256 = 0x40,
     Osr512 = 0x42,
     Osr1024 = 0x44,
     Osr2048 = 0x46,
+    #[default]
     Osr4096 = 0x48,
 }
 
 impl OversampleRatio {
-    fn delay_ms(&self) -> u32 {
+    /// Time to wait for a conversion at this oversample ratio, in whole milliseconds: the
+    /// datasheet maximum from [`OversampleRatio::delay_us`] rounded up, so it never waits less.
+    ///
+    /// Both are `const fn`, so conversion times can size timer slots at compile time:
+    ///
+    /// ```
+    /// use ms5611_rs::OversampleRatio;
+    ///
+    /// const SLOT_US: u32 = OversampleRatio::Osr4096.delay_us() * 2;
+    /// assert_eq!(SLOT_US, 18_080);
+    /// ```
+    pub const fn delay_ms(&self) -> u32 {
         match self {
             OversampleRatio::Osr256 => 1,
             OversampleRatio::Osr512 => 2,
@@ -24,11 +84,228 @@ impl OversampleRatio {
             OversampleRatio::Osr4096 => 10,
         }
     }
+
+    /// Maximum conversion time at this oversample ratio from the datasheet, in microseconds. This
+    /// is what the driver waits between starting a conversion and reading the ADC.
+    ///
+    /// These are the datasheet maximums rather than the typical values, with no extra margin. The
+    /// bus speed doesn't change them: the conversion runs inside the sensor and the wait starts
+    /// once the command has been sent, so a faster bus can't make the read-back early. Reading
+    /// too early returns an ADC value of 0, reported as [`Ms5611Error::ConversionNotReady`],
+    /// which usually means the delay provider runs short.
+    pub const fn delay_us(&self) -> u32 {
+        match self {
+            OversampleRatio::Osr256 => 600,
+            OversampleRatio::Osr512 => 1170,
+            OversampleRatio::Osr1024 => 2280,
+            OversampleRatio::Osr2048 => 4540,
+            OversampleRatio::Osr4096 => 9040,
+        }
+    }
+}
+
+/// Converts from the D1 (pressure) conversion command byte, e.g. `0x48` for OSR 4096.
+impl TryFrom<u8> for OversampleRatio {
+    type Error = InvalidOversampleRatio;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x40 => Ok(OversampleRatio::Osr256),
+            0x42 => Ok(OversampleRatio::Osr512),
+            0x44 => Ok(OversampleRatio::Osr1024),
+            0x46 => Ok(OversampleRatio::Osr2048),
+            0x48 => Ok(OversampleRatio::Osr4096),
+            _ => Err(InvalidOversampleRatio(value)),
+        }
+    }
+}
+
+impl From<OversampleRatio> for u8 {
+    fn from(osr: OversampleRatio) -> Self {
+        osr as u8
+    }
 }
 
-use embedded_hal::i2c::blocking::I2c;
+/// The byte passed to `OversampleRatio::try_from` isn't one of the conversion command values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidOversampleRatio(pub u8);
+
+/// The ADC channel a conversion is performed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertChannel {
+    /// Digital pressure value, D1.
+    Pressure,
+    /// Digital temperature value, D2.
+    Temperature,
+}
 
-struct Prom {
+/// The sensor's command set, from the datasheet command table. Every command is a single byte,
+/// given by [`Command::byte`] or `u8::from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Reloads the PROM into the internal registers, 0x1E.
+    Reset,
+    /// Reads back the result of the last conversion, 0x00.
+    AdcRead,
+    /// Reads PROM word `index`, which must be in 0..=7, 0xA0 to 0xAE.
+    PromRead(u8),
+    /// Starts a conversion of the channel at the oversample ratio, 0x40 to 0x58.
+    Convert(ConvertChannel, OversampleRatio),
+}
+
+impl Command {
+    pub const fn byte(self) -> u8 {
+        match self {
+            Command::Reset => 0b0001_1110,
+            Command::AdcRead => 0b0000_0000,
+            Command::PromRead(index) => {
+                debug_assert!(index < 8, "the PROM only has eight words");
+
+                0b1010_0000 | (index << 1)
+            }
+            Command::Convert(ConvertChannel::Pressure, osr) => osr as u8,
+            Command::Convert(ConvertChannel::Temperature, osr) => osr as u8 | MS5611_CONVERT_D2,
+        }
+    }
+}
+
+impl From<Command> for u8 {
+    fn from(command: Command) -> u8 {
+        command.byte()
+    }
+}
+
+/// Returns the command byte that starts a conversion of `channel` at `osr`, e.g. `0x48` for a
+/// pressure conversion at OSR 4096 and `0x58` for the matching temperature conversion.
+pub const fn convert_command(channel: ConvertChannel, osr: OversampleRatio) -> u8 {
+    Command::Convert(channel, osr).byte()
+}
+
+// The conversion commands from the datasheet command table, checked at compile time so a typo in
+// the discriminants can't send the wrong command
+const _: () = {
+    use ConvertChannel::{Pressure, Temperature};
+    use OversampleRatio::*;
+
+    assert!(Command::Reset.byte() == 0x1E);
+    assert!(Command::AdcRead.byte() == 0x00);
+    assert!(Command::PromRead(0).byte() == 0xA0);
+    assert!(Command::PromRead(7).byte() == 0xAE);
+
+    assert!(convert_command(Pressure, Osr256) == 0x40);
+    assert!(convert_command(Pressure, Osr512) == 0x42);
+    assert!(convert_command(Pressure, Osr1024) == 0x44);
+    assert!(convert_command(Pressure, Osr2048) == 0x46);
+    assert!(convert_command(Pressure, Osr4096) == 0x48);
+    assert!(convert_command(Temperature, Osr256) == 0x50);
+    assert!(convert_command(Temperature, Osr512) == 0x52);
+    assert!(convert_command(Temperature, Osr1024) == 0x54);
+    assert!(convert_command(Temperature, Osr2048) == 0x56);
+    assert!(convert_command(Temperature, Osr4096) == 0x58);
+
+    assert!(Osr256.delay_ms() == Osr256.delay_us().div_ceil(1000));
+    assert!(Osr512.delay_ms() == Osr512.delay_us().div_ceil(1000));
+    assert!(Osr1024.delay_ms() == Osr1024.delay_us().div_ceil(1000));
+    assert!(Osr2048.delay_ms() == Osr2048.delay_us().div_ceil(1000));
+    assert!(Osr4096.delay_ms() == Osr4096.delay_us().div_ceil(1000));
+};
+
+/// Sensor part number. The MS5607 shares the command set, PROM layout and CRC with the MS5611 but
+/// scales its offset and sensitivity differently during compensation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Ms5611,
+    Ms5607,
+}
+
+use core::fmt;
+use core::num::NonZeroU32;
+
+#[cfg(feature = "spi")]
+use embedded_hal::digital::OutputPin;
+#[cfg(feature = "i2c")]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "spi")]
+use embedded_hal::spi::SpiBus;
+
+use delay::{StoredDelay, Wait};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Ms5611Error<E> {
+    /// Error from the underlying bus (I2C or SPI).
+    Bus(E),
+    /// The CRC nibble stored in PROM word 7 did not match the CRC computed over the PROM.
+    CrcMismatch { expected: u16, computed: u16 },
+    /// A measurement was requested before the PROM calibration was read.
+    PromNotRead,
+    /// A compensated pressure was requested before any temperature conversion was performed.
+    TemperatureNotRead,
+    /// A conversion result was requested without first starting a conversion.
+    NoConversionPending,
+    /// The ADC read back as 0, which the sensor returns when the conversion hasn't finished or
+    /// wasn't started. The conversion has to be restarted, with a longer delay if it was too short.
+    ConversionNotReady,
+    /// The ADC read back as 0xFFFFFF. The sensor never produces this for a real conversion, so it
+    /// points at a disconnected or faulty sensor, or a bad connection.
+    AdcSaturated,
+    /// The same raw pressure value was read back more times in a row than allowed by
+    /// [`Ms5611::set_stuck_reading_threshold`], which suggests the ADC has wedged. Resetting the
+    /// sensor usually recovers it.
+    StuckReading,
+    /// The compensated reading falls outside the sensor's operating range, see
+    /// [`Ms5611::set_range_check`]. Carries the rejected reading.
+    OutOfRange(Measurement),
+    /// The calibration coefficients C1 to C6 all read back as 0x0000 or all as 0xFFFF, which is
+    /// what a missing, unpowered or unprogrammed sensor returns. Checked before the CRC.
+    InvalidCalibration,
+    /// [`Ms5611::read_temperature_first`] found the temperature outside the band set with
+    /// [`Ms5611::set_temperature_band`] and skipped the pressure conversion. Carries the
+    /// temperature in degrees Celsius * 100.
+    TemperatureOutOfBand(i32),
+}
+
+/// A reading with the raw ADC values and intermediate it was computed from, returned by
+/// [`Ms5611::read_diagnostic`]. Logging the raw values allows re-running the compensation later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticMeasurement {
+    /// Raw pressure value, D1.
+    pub d1: u32,
+    /// Raw temperature value, D2.
+    pub d2: u32,
+    /// Difference between the actual and reference temperature, from the datasheet, dT.
+    pub dt: i32,
+    /// The compensated reading.
+    pub measurement: Measurement,
+}
+
+/// Which second-order temperature corrections were applied to a reading, returned by
+/// [`Ms5611::read_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SecondOrderCompensation {
+    /// The below 20 °C correction was applied.
+    pub low_temperature: bool,
+    /// The additional below -15 °C correction was applied.
+    pub very_low_temperature: bool,
+    /// Total offset correction subtracted from OFF, from the datasheet, OFF2.
+    pub offset: i64,
+    /// Total sensitivity correction subtracted from SENS, from the datasheet, SENS2.
+    pub sensitivity: i64,
+    /// Temperature correction subtracted from TEMP, in degrees Celsius * 100, from the datasheet,
+    /// T2.
+    pub temperature: i32,
+}
+
+/// Factory calibration coefficients stored in the sensor's PROM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
     /// From datasheet, C1.
     pub pressure_sensitivity: u16,
     /// From datasheet, C2.
@@ -43,158 +320,1771 @@ struct Prom {
     pub temp_coef_temp: u16,
 }
 
-pub struct Ms5611<I2C> {
-    address: u8,
-    i2c: I2C,
-    prom: Option<Prom>,
+/// A compensated pressure and temperature reading.
+///
+/// The field order and integer types are part of the serialized format and won't change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measurement {
+    /// Pressure in mbar * 100 (i.e. Pa).
+    pub pressure_mbar_x100: u32,
+    /// Temperature in degrees Celsius * 100.
+    pub temperature_c_x100: i32,
 }
 
-impl<I2C: I2c> Ms5611<I2C> {
+/// The datasheet's operating range, in the same units as the fields and in whole units for display
+/// or configuration code. These are the limits [`Ms5611::set_range_check`] enforces.
+impl Measurement {
+    /// Lowest pressure in the datasheet's operating range, in mbar.
+    pub const MIN_PRESSURE_MBAR: u32 = 10;
+    /// Highest pressure in the datasheet's operating range, in mbar.
+    pub const MAX_PRESSURE_MBAR: u32 = 1200;
+    /// Lowest temperature in the datasheet's operating range, in degrees Celsius.
+    pub const MIN_TEMPERATURE_C: i32 = -40;
+    /// Highest temperature in the datasheet's operating range, in degrees Celsius.
+    pub const MAX_TEMPERATURE_C: i32 = 85;
+
+    /// [`Measurement::MIN_PRESSURE_MBAR`] in mbar * 100.
+    pub const MIN_PRESSURE_MBAR_X100: u32 = Self::MIN_PRESSURE_MBAR * 100;
+    /// [`Measurement::MAX_PRESSURE_MBAR`] in mbar * 100.
+    pub const MAX_PRESSURE_MBAR_X100: u32 = Self::MAX_PRESSURE_MBAR * 100;
+    /// [`Measurement::MIN_TEMPERATURE_C`] in degrees Celsius * 100.
+    pub const MIN_TEMPERATURE_C_X100: i32 = Self::MIN_TEMPERATURE_C * 100;
+    /// [`Measurement::MAX_TEMPERATURE_C`] in degrees Celsius * 100.
+    pub const MAX_TEMPERATURE_C_X100: i32 = Self::MAX_TEMPERATURE_C * 100;
+
+    /// Returns `true` if both pressure and temperature are within the datasheet's operating
+    /// range. Readings outside it are either out of spec or the result of a faulty conversion.
+    pub fn is_in_operating_range(&self) -> bool {
+        (Self::MIN_PRESSURE_MBAR_X100..=Self::MAX_PRESSURE_MBAR_X100)
+            .contains(&self.pressure_mbar_x100)
+            && (Self::MIN_TEMPERATURE_C_X100..=Self::MAX_TEMPERATURE_C_X100)
+                .contains(&self.temperature_c_x100)
+    }
+}
+
+/// Formats as e.g. `1013.25 hPa, 21.37 °C` using integer math only.
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pressure = self.pressure_mbar_x100;
+        let sign = if self.temperature_c_x100 < 0 { "-" } else { "" };
+        let temperature = self.temperature_c_x100.unsigned_abs();
+
+        write!(
+            f,
+            "{}.{:02} hPa, {}{}.{:02} °C",
+            pressure / 100,
+            pressure % 100,
+            sign,
+            temperature / 100,
+            temperature % 100
+        )
+    }
+}
+
+#[cfg(feature = "float")]
+impl Measurement {
+    /// Pressure in pascals.
+    pub fn pressure_pa(&self) -> f32 {
+        self.pressure_mbar_x100 as f32
+    }
+
+    /// Pressure in hectopascals (equivalent to mbar).
+    pub fn pressure_hpa(&self) -> f32 {
+        self.pressure_mbar_x100 as f32 / 100.0
+    }
+
+    /// Temperature in degrees Celsius.
+    pub fn temperature_celsius(&self) -> f32 {
+        self.temperature_c_x100 as f32 / 100.0
+    }
+
+    /// Temperature in degrees Fahrenheit.
+    ///
+    /// ```
+    /// # use ms5611_rs::Measurement;
+    /// let measurement = Measurement {
+    ///     pressure_mbar_x100: 101_325,
+    ///     temperature_c_x100: 2000,
+    /// };
+    ///
+    /// assert_eq!(measurement.temperature_fahrenheit(), 68.0);
+    /// assert!((measurement.temperature_kelvin() - 293.15).abs() < 1e-3);
+    /// ```
+    pub fn temperature_fahrenheit(&self) -> f32 {
+        self.temperature_celsius() * 9.0 / 5.0 + 32.0
+    }
+
+    /// Temperature in kelvin.
+    pub fn temperature_kelvin(&self) -> f32 {
+        self.temperature_celsius() + 273.15
+    }
+}
+
+#[cfg(feature = "uom")]
+impl Measurement {
+    /// Pressure as a typed `uom` quantity.
+    pub fn pressure_quantity(&self) -> uom::si::f32::Pressure {
+        uom::si::f32::Pressure::new::<uom::si::pressure::pascal>(self.pressure_mbar_x100 as f32)
+    }
+
+    /// Temperature as a typed `uom` quantity.
+    pub fn temperature_quantity(&self) -> uom::si::f32::ThermodynamicTemperature {
+        uom::si::f32::ThermodynamicTemperature::new::<
+            uom::si::thermodynamic_temperature::degree_celsius,
+        >(self.temperature_c_x100 as f32 / 100.0)
+    }
+}
+
+/// Driver for an MS5611 (or MS5607) on an [`Interface`].
+///
+/// The driver holds no references, interior mutability or raw pointers of its own, so it is
+/// `Send` and `Sync` whenever the interface and delay provider are. All operations take
+/// `&mut self`, so sharing it between an interrupt and a task needs the usual mutex, e.g. a
+/// critical-section `Mutex<RefCell<Ms5611<..>>>`, which also keeps a read and its conversion wait
+/// from being interleaved with another caller's commands.
+pub struct Ms5611<I, DELAY = ()> {
+    interface: I,
+    /// Delay provider used by [`Ms5611::read_configured`], `()` for drivers built without one.
+    delay: DELAY,
+    /// Oversample ratio used by [`Ms5611::read_configured`].
+    osr: OversampleRatio,
+    prom: Option<Calibration>,
+    /// Raw PROM contents as captured by the last [`Ms5611::read_prom`], word 0 first.
+    prom_bytes: Option<[u8; 16]>,
+    variant: Variant,
+    second_order_compensation: bool,
+    /// Most recent raw temperature (D2), reused to compensate pressure-only reads.
+    last_d2: Option<u32>,
+    /// Pressure conversions performed since `last_d2` was taken.
+    pressure_reads_since_temperature: u32,
+    /// Conversion started with [`Ms5611::start_conversion`] whose result hasn't been read yet.
+    pending: Option<(ConvertChannel, OversampleRatio)>,
+    /// Running exponential moving average of pressure and temperature, scaled by 256.
+    filter: Option<(i64, i64)>,
+    /// Reject compensated readings outside the datasheet's operating range.
+    range_check: bool,
+    /// Inclusive temperature band checked by [`Ms5611::read_temperature_first`], in degrees
+    /// Celsius * 100.
+    temperature_band: (i32, i32),
+    /// Time [`Ms5611::init`] waits before the reset, in milliseconds.
+    power_on_delay_ms: u32,
+    /// Conversions discarded by [`Ms5611::init`] while the sensor settles.
+    warm_up_samples: u8,
+    /// PROM read retries used by [`Ms5611::init`].
+    prom_retries: u8,
+    /// Times a conversion is restarted after a bus error.
+    adc_retries: u8,
+    /// Extra time added to every conversion wait, in percent of the datasheet maximum.
+    delay_margin_percent: u32,
+    /// Number of identical consecutive D1 values that is reported as a stuck reading.
+    stuck_threshold: Option<NonZeroU32>,
+    /// Most recent raw pressure (D1) and how many times in a row it has been read.
+    last_d1: Option<(u32, u32)>,
+    /// Lowest and highest compensated pressure since the last reset, in mbar * 100.
+    pressure_extremes: Option<(u32, u32)>,
+    /// Sea-level reference pressure used for altitude, in pascals.
+    #[cfg(feature = "float")]
+    sea_level_pa: f32,
+}
+
+impl<I: fmt::Debug, DELAY> fmt::Debug for Ms5611<I, DELAY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ms5611")
+            .field("interface", &self.interface)
+            .field("osr", &self.osr)
+            .field("variant", &self.variant)
+            .field("initialized", &self.prom.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl<I2C: I2c> Ms5611<I2cInterface<I2C>> {
     pub fn new(i2c: I2C, address: u8) -> Self {
+        Ms5611::with_interface(I2cInterface::new(i2c, address))
+    }
+
+    pub fn with_address(i2c: I2C, address: Ms5611Address) -> Self {
+        Ms5611::new(i2c, address.into())
+    }
+
+    /// Creates the driver and runs [`Ms5611::init`], so the returned driver is ready to read.
+    /// Fails if the sensor doesn't respond or its PROM is corrupted, dropping the bus handle.
+    ///
+    /// `power_on_delay_ms` is waited before the reset, see [`Ms5611::set_power_on_delay_ms`].
+    /// Pass 0 if the sensor has been powered for a while.
+    pub fn try_new<D: ConversionDelay>(
+        i2c: I2C,
+        address: u8,
+        power_on_delay_ms: u32,
+        delay: &mut D,
+    ) -> Result<Self, Ms5611Error<I2C::Error>> {
+        let mut ms5611 = Ms5611::new(i2c, address);
+        ms5611.set_power_on_delay_ms(power_on_delay_ms);
+        ms5611.init(delay)?;
+
+        Ok(ms5611)
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl<I2C: I2c, DELAY> Ms5611<I2cInterface<I2C>, DELAY> {
+    pub fn address(&self) -> u8 {
+        self.interface.address()
+    }
+
+    /// Consumes the driver and returns the I2C bus handle, e.g. to reconfigure the peripheral or
+    /// hand it to another driver.
+    ///
+    /// The driver never assumes it owns the whole bus, so it can also be given a shared handle
+    /// such as `&mut I2C` or an `embedded-hal-bus` device:
+    ///
+    /// ```
+    /// use core::cell::RefCell;
+    /// use embedded_hal::i2c::I2c;
+    /// use embedded_hal_bus::i2c::RefCellDevice;
+    /// use ms5611_rs::{Ms5611, Ms5611Address, Ms5611Error};
+    ///
+    /// fn read_both<I2C: I2c>(i2c: I2C) -> Result<I2C, Ms5611Error<I2C::Error>> {
+    ///     let bus = RefCell::new(i2c);
+    ///     let mut upper = Ms5611::with_address(RefCellDevice::new(&bus), Ms5611Address::Csb0);
+    ///     let mut lower = Ms5611::with_address(RefCellDevice::new(&bus), Ms5611Address::Csb1);
+    ///
+    ///     upper.read_prom()?;
+    ///     lower.read_prom()?;
+    ///
+    ///     upper.release();
+    ///     lower.release();
+    ///
+    ///     Ok(bus.into_inner())
+    /// }
+    /// ```
+    pub fn release(self) -> I2C {
+        self.interface.release()
+    }
+
+    /// Changes the I2C address the driver talks to. The calibration and any other state cached
+    /// from the previous device is discarded, so [`Ms5611::read_prom`] must be called again
+    /// before reading.
+    pub fn set_address(&mut self, address: u8) {
+        self.interface.set_address(address);
+        self.prom = None;
+        self.prom_bytes = None;
+        self.last_d2 = None;
+        self.pressure_reads_since_temperature = 0;
+        self.pending = None;
+        self.filter = None;
+        self.last_d1 = None;
+        self.pressure_extremes = None;
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<SPI: SpiBus, CS: OutputPin> Ms5611<SpiInterface<SPI, CS>> {
+    pub fn new_spi(spi: SPI, cs: CS) -> Self {
+        Ms5611::with_interface(SpiInterface::new(spi, cs))
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<SPI: SpiBus, CS: OutputPin, DELAY> Ms5611<SpiInterface<SPI, CS>, DELAY> {
+    /// Consumes the driver and returns the SPI bus and chip-select pin.
+    pub fn release(self) -> (SPI, CS) {
+        self.interface.release()
+    }
+}
+
+impl<I: Interface> Ms5611<I> {
+    pub fn with_interface(interface: I) -> Self {
+        Ms5611::with_config(interface, OversampleRatio::default(), ())
+    }
+}
+
+impl<I: Interface, DELAY> Ms5611<I, DELAY> {
+    fn with_config(interface: I, osr: OversampleRatio, delay: DELAY) -> Self {
         Ms5611 {
-            address,
-            i2c,
+            interface,
+            delay,
+            osr,
             prom: None,
+            prom_bytes: None,
+            variant: Variant::Ms5611,
+            second_order_compensation: true,
+            last_d2: None,
+            pressure_reads_since_temperature: 0,
+            pending: None,
+            filter: None,
+            range_check: false,
+            temperature_band: (
+                Measurement::MIN_TEMPERATURE_C_X100,
+                Measurement::MAX_TEMPERATURE_C_X100,
+            ),
+            power_on_delay_ms: 0,
+            warm_up_samples: 0,
+            prom_retries: 0,
+            adc_retries: 0,
+            delay_margin_percent: 0,
+            stuck_threshold: None,
+            last_d1: None,
+            pressure_extremes: None,
+            #[cfg(feature = "float")]
+            sea_level_pa: STANDARD_SEA_LEVEL_PA,
         }
     }
 
-    pub fn reset(&mut self) -> Result<(), I2C::Error> {
-        self.i2c.write(self.address, &[MS5611_RESET])
+    /// Sends the reset command. The sensor needs ~2.8 ms to reload its PROM afterwards, so wait
+    /// before calling [`Ms5611::read_prom`] or use [`Ms5611::reset_and_wait`] instead.
+    pub fn reset(&mut self) -> Result<(), Ms5611Error<I::Error>> {
+        self.interface
+            .write_command(Command::Reset.byte())
+            .map_err(Ms5611Error::Bus)
     }
 
-    pub fn read_prom(&mut self) -> Result<(), I2C::Error> {
-        let mut buf: [u8; 2] = [0u8; 2];
-        let mut prom = Prom {
-            pressure_sensitivity: 0,
-            pressure_offset: 0,
-            temp_coef_pressure_sensitivity: 0,
-            temp_coef_pressure_offset: 0,
-            temp_ref: 0,
-            temp_coef_temp: 0,
-        };
+    /// Sends the reset command and waits until the PROM can be read.
+    pub fn reset_and_wait<D: ConversionDelay>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Ms5611Error<I::Error>> {
+        self.reset()?;
+        delay.delay_ms(MS5611_RESET_DELAY_MS);
+
+        Ok(())
+    }
+
+    /// Brings the sensor up in one call: resets it, waits for the PROM to reload, then reads and
+    /// verifies the PROM. Afterwards the driver is ready to [`Ms5611::read`].
+    ///
+    /// If [`Ms5611::set_power_on_delay_ms`] was configured, that long is waited before the reset.
+    /// If [`Ms5611::set_warm_up_samples`] was configured, that many pressure and temperature
+    /// conversions are performed and thrown away before returning.
+    pub fn init<D: ConversionDelay>(&mut self, delay: &mut D) -> Result<(), Ms5611Error<I::Error>> {
+        if self.power_on_delay_ms > 0 {
+            delay.delay_ms(self.power_on_delay_ms);
+        }
+        self.reset_and_wait(delay)?;
+        self.read_prom_with_retries(self.prom_retries, delay)?;
+
+        for _ in 0..self.warm_up_samples {
+            self.read_raw(self.osr, delay)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets how many times [`Ms5611::init`] retries each PROM word that fails on the bus, see
+    /// [`Ms5611::read_prom_with_retries`]. Defaults to 0.
+    pub fn set_prom_retries(&mut self, retries: u8) {
+        self.prom_retries = retries;
+    }
+
+    /// Sets how many times a conversion is retried when the command write or the ADC read fails on
+    /// the bus. Each retry restarts the conversion, or both conversions for a full read, and
+    /// waits for it again. Once the retries are exhausted the last bus error is returned. Defaults
+    /// to 0.
+    pub fn set_adc_retries(&mut self, retries: u8) {
+        self.adc_retries = retries;
+    }
+
+    /// Lengthens every conversion wait by `percent` of the datasheet maximum, e.g. 20 to wait
+    /// 1.2 times as long. Useful when the delay provider runs off an imprecise clock that can
+    /// finish early. Also applies to [`Ms5611::is_ready`]. Defaults to 0.
+    pub fn set_delay_margin_percent(&mut self, percent: u32) {
+        self.delay_margin_percent = percent;
+    }
+
+    /// Conversion wait at `osr` including the configured margin, in microseconds.
+    fn conversion_delay_us(&self, osr: OversampleRatio) -> u32 {
+        conversion_delay_us(osr, self.delay_margin_percent)
+    }
+
+    /// Sets the inclusive temperature band, in degrees Celsius * 100, outside of which
+    /// [`Ms5611::read_temperature_first`] returns early. Defaults to the datasheet's operating
+    /// range.
+    pub fn set_temperature_band(&mut self, min_c_x100: i32, max_c_x100: i32) {
+        self.temperature_band = (min_c_x100, max_c_x100);
+    }
+
+    /// Sets how long [`Ms5611::init`] waits before sending the reset, for boards that switch the
+    /// sensor's supply on just before initializing it. A reset sent before the sensor's
+    /// power-on reset has finished is ignored. Defaults to 0.
+    pub fn set_power_on_delay_ms(&mut self, ms: u32) {
+        self.power_on_delay_ms = ms;
+    }
+
+    /// Sets how many readings [`Ms5611::init`] discards after a reset. The first conversions after
+    /// power-up can be off while the internal reference settles; 2 is usually enough. Defaults to
+    /// 0.
+    pub fn set_warm_up_samples(&mut self, samples: u8) {
+        self.warm_up_samples = samples;
+    }
+
+    /// Returns the calibration coefficients, or `None` if the PROM hasn't been read yet.
+    pub fn calibration(&self) -> Option<Calibration> {
+        self.prom
+    }
+
+    /// Loads previously stored calibration coefficients without touching the bus, so `read` can be
+    /// used immediately. This is the inverse of [`Ms5611::calibration`].
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.prom = Some(calibration);
+        self.prom_bytes = None;
+    }
+
+    /// Returns all eight raw PROM words from the last successful [`Ms5611::read_prom`], including
+    /// the factory data in word 0 and the CRC in word 7. Returns `None` if the current calibration
+    /// didn't come from `read_prom`. Handy for logging a complete fingerprint of the device.
+    pub fn prom_words(&self) -> Option<[u16; 8]> {
+        self.prom_bytes.as_ref().map(prom_words_from_bytes)
+    }
+
+    /// The same PROM contents as [`Ms5611::prom_words`], as the 16 big-endian bytes exactly as
+    /// they were read from the sensor.
+    pub fn prom_bytes(&self) -> Option<&[u8; 16]> {
+        self.prom_bytes.as_ref()
+    }
+
+    /// Sets the oversample ratio used by [`Ms5611::read_configured`].
+    pub fn set_oversample_ratio(&mut self, osr: OversampleRatio) {
+        self.osr = osr;
+    }
+
+    /// Selects which part the compensation math is applied for (MS5611 by default).
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+
+    /// Enables or disables the second-order temperature compensation (enabled by default).
+    ///
+    /// The second-order correction only changes the result below 20 °C. It lowers the temperature
+    /// by T2 and corrects the pressure offset and sensitivity by OFF2 and SENS2. Skipping it
+    /// leaves both readings too high by an error that grows as the sensor gets colder, about
+    /// 1.6 °C at 0 °C. Above 20 °C results are identical either way.
+    pub fn set_second_order_compensation(&mut self, enabled: bool) {
+        self.second_order_compensation = enabled;
+    }
+
+    /// When enabled, compensated readings outside the datasheet's operating range
+    /// ([`Measurement::MIN_PRESSURE_MBAR`] to [`Measurement::MAX_PRESSURE_MBAR`] and
+    /// [`Measurement::MIN_TEMPERATURE_C`] to [`Measurement::MAX_TEMPERATURE_C`]) are returned as
+    /// [`Ms5611Error::OutOfRange`] instead. Disabled by default.
+    pub fn set_range_check(&mut self, enabled: bool) {
+        self.range_check = enabled;
+    }
+
+    /// Reports [`Ms5611Error::StuckReading`] once `threshold` identical raw pressure values have
+    /// been read in a row, so a threshold of 2 fails on the first exact repeat. `None`, the
+    /// default, disables the check, and so does a threshold of 1, since every value is trivially
+    /// one in a row with itself.
+    ///
+    /// Real readings carry enough noise that identical consecutive values are rare, but pick a
+    /// threshold with some headroom at low oversample ratios on a very stable signal.
+    pub fn set_stuck_reading_threshold(&mut self, threshold: Option<NonZeroU32>) {
+        self.stuck_threshold = threshold.filter(|threshold| threshold.get() > 1);
+        self.last_d1 = None;
+    }
+
+    /// Reads all eight PROM words, checks their CRC and loads the calibration.
+    ///
+    /// The sensor doesn't auto-increment the PROM address, so every word needs its own command,
+    /// but the I2C interface chains all of them into one bus transaction. The words land in a
+    /// single 16-byte buffer that is kept for [`Ms5611::prom_bytes`]. The CRC covers every word,
+    /// the reserved one included, so verifying it always takes the full eight-word read.
+    pub fn read_prom(&mut self) -> Result<(), Ms5611Error<I::Error>> {
+        let (calibration, buf) = self.fetch_prom()?;
+
+        self.prom = Some(calibration);
+        self.prom_bytes = Some(buf);
+
+        Ok(())
+    }
+
+    /// Reads and verifies the PROM like [`Ms5611::read_prom`], but returns the calibration instead
+    /// of loading it, so it can be compared against a stored copy before committing to it with
+    /// [`Ms5611::set_calibration`]. The driver's state is left untouched.
+    pub fn fetch_calibration(&mut self) -> Result<Calibration, Ms5611Error<I::Error>> {
+        self.fetch_prom().map(|(calibration, _)| calibration)
+    }
+
+    /// Re-reads the PROM and checks it still matches the loaded calibration, e.g. as a periodic
+    /// health check for corruption after a power or EMI event. When the calibration came from
+    /// [`Ms5611::read_prom`], the reserved and CRC words are compared too. The loaded calibration
+    /// is kept either way.
+    pub fn verify_calibration(&mut self) -> Result<bool, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let mut buf = [0u8; 16];
+        self.interface
+            .read_prom(&mut buf)
+            .map_err(Ms5611Error::Bus)?;
+
+        Ok(calibration_from_words(&prom_words_from_bytes(&buf)) == prom
+            && self.prom_bytes.is_none_or(|bytes| bytes == buf))
+    }
+
+    fn fetch_prom(&mut self) -> Result<(Calibration, [u8; 16]), Ms5611Error<I::Error>> {
+        let mut buf = [0u8; 16];
+        self.interface
+            .read_prom(&mut buf)
+            .map_err(Ms5611Error::Bus)?;
+
+        Ok((calibration_from_prom(&prom_words_from_bytes(&buf))?, buf))
+    }
+
+    /// Like [`Ms5611::read_prom`], but reads each PROM word in its own transaction and retries a
+    /// word up to `retries` times if it fails on the bus, e.g. because a transfer was NAKed, so
+    /// one transient error doesn't restart the whole read. Waits 1 ms before a word's first retry
+    /// and doubles the wait for each further one, up to 16 ms. The CRC is checked once all eight
+    /// words are in, and a mismatch is returned straight away.
+    ///
+    /// With `retries` set to 0 this is exactly [`Ms5611::read_prom`], single transaction included.
+    pub fn read_prom_with_retries<D: ConversionDelay>(
+        &mut self,
+        retries: u8,
+        delay: &mut D,
+    ) -> Result<(), Ms5611Error<I::Error>> {
+        if retries == 0 {
+            return self.read_prom();
+        }
+
+        let mut buf = [0u8; 16];
+        for (index, word) in (0u8..).zip(buf.chunks_exact_mut(2)) {
+            let mut backoff_ms = 1;
+            let mut attempt = 0;
+
+            let value = loop {
+                match self.read_prom_word(index) {
+                    Err(Ms5611Error::Bus(_)) if attempt < retries => {
+                        delay.delay_ms(backoff_ms);
+                        backoff_ms = (backoff_ms * 2).min(16);
+                        attempt += 1;
+                    }
+                    result => break result?,
+                }
+            };
+            word.copy_from_slice(&value.to_be_bytes());
+        }
+
+        self.prom = Some(calibration_from_prom(&prom_words_from_bytes(&buf))?);
+        self.prom_bytes = Some(buf);
+
+        Ok(())
+    }
+
+    /// Reads only the coefficient words C1 to C6 and loads the calibration, skipping the reserved
+    /// word 0 and the CRC word 7. This saves two transactions and the CRC computation at the cost
+    /// of silently accepting corrupted coefficients, so prefer [`Ms5611::read_prom`] unless boot
+    /// time is critical.
+    pub fn read_prom_unchecked(&mut self) -> Result<(), Ms5611Error<I::Error>> {
+        let mut words = [0u16; 7];
+
+        for (index, word) in (1u8..).zip(words[1..].iter_mut()) {
+            *word = self.read_prom_word(index)?;
+        }
+
+        self.prom = Some(calibration_from_words(&words));
+        self.prom_bytes = None;
+
+        Ok(())
+    }
+
+    /// Probes the sensor by reading the PROM and checking the calibration coefficients aren't all
+    /// 0x0000 or all 0xFFFF, which is what a floating or missing device reads back as. The PROM is
+    /// not stored. Bus errors that mean nothing answered, like an I2C address NAK, also return
+    /// `Ok(false)`; any other bus error is returned as [`Ms5611Error::Bus`].
+    pub fn is_connected(&mut self) -> Result<bool, Ms5611Error<I::Error>> {
+        let mut buf = [0u8; 16];
+
+        match self.interface.read_prom(&mut buf) {
+            Ok(()) => Ok(!coefficients_blank(&prom_words_from_bytes(&buf))),
+            Err(error) if I::is_absent_device_error(&error) => Ok(false),
+            Err(error) => Err(Ms5611Error::Bus(error)),
+        }
+    }
+
+    fn read_prom_word(&mut self, index: u8) -> Result<u16, Ms5611Error<I::Error>> {
+        let mut buf = [0u8; 2];
+        self.interface
+            .command_read(Command::PromRead(index).byte(), &mut buf)
+            .map_err(Ms5611Error::Bus)?;
+
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    /// Performs a pressure conversion and returns the raw 24-bit D1 value.
+    pub fn read_pressure_raw<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<u32, Ms5611Error<I::Error>> {
+        let d1 = self.convert(ConvertChannel::Pressure, osr, delay)?;
+        self.record_conversion(ConvertChannel::Pressure, d1)?;
+
+        Ok(d1)
+    }
+
+    /// Performs a temperature conversion and returns the raw 24-bit D2 value.
+    ///
+    /// Doesn't need the PROM, so straight after power-up it doubles as a quick liveness check: a
+    /// sensor that isn't responding fails the bus transfer or reads back 0 or 0xFFFFFF, which are
+    /// reported as errors.
+    pub fn read_temperature_raw<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<u32, Ms5611Error<I::Error>> {
+        let d2 = self.convert(ConvertChannel::Temperature, osr, delay)?;
+        self.record_conversion(ConvertChannel::Temperature, d2)?;
+
+        Ok(d2)
+    }
+
+    /// Performs a temperature conversion and returns the temperature in degrees Celsius * 100.
+    pub fn read_temperature<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<i32, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+        let d2 = self.read_temperature_raw(osr, delay)?;
+        let (dt, temperature) = first_order_temperature(&prom, d2);
+
+        if self.second_order_compensation {
+            Ok(temperature - second_order_temperature(dt, temperature))
+        } else {
+            Ok(temperature)
+        }
+    }
+
+    /// Performs only a pressure conversion and compensates it with the most recent temperature
+    /// conversion, halving the conversion time compared to [`Ms5611::read`]. Returns
+    /// [`Ms5611Error::TemperatureNotRead`] if no temperature has been read yet.
+    pub fn read_pressure_compensated<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let measurement = self.read_pressure_untracked(osr, delay)?;
+        self.track_extremes(&measurement);
+
+        Ok(measurement)
+    }
+
+    /// [`Ms5611::read_pressure_compensated`] without feeding the pressure extremes, for reads
+    /// that combine several samples into the value they return.
+    fn read_pressure_untracked<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        if self.prom.is_none() {
+            return Err(Ms5611Error::PromNotRead);
+        }
+        if self.last_d2.is_none() {
+            return Err(Ms5611Error::TemperatureNotRead);
+        }
+
+        let d1 = self.read_pressure_raw(osr, delay)?;
+
+        self.compensate_pressure(d1)
+    }
+
+    /// Starts a conversion and returns immediately. Once the conversion time of `osr` has elapsed
+    /// (see [`Ms5611::is_ready`]), fetch the raw value with [`Ms5611::read_result`].
+    ///
+    /// Any other command sent to the sensor while the conversion is running will corrupt it.
+    pub fn start_conversion(
+        &mut self,
+        osr: OversampleRatio,
+        channel: ConvertChannel,
+    ) -> Result<(), Ms5611Error<I::Error>> {
+        self.interface
+            .write_command(convert_command(channel, osr))
+            .map_err(Ms5611Error::Bus)?;
+        self.pending = Some((channel, osr));
+
+        Ok(())
+    }
+
+    /// Returns true if a conversion is pending and `elapsed_ms` milliseconds, measured by the
+    /// caller since [`Ms5611::start_conversion`], cover its conversion time.
+    pub fn is_ready(&self, elapsed_ms: u32) -> bool {
+        match self.pending {
+            Some((_, osr)) => elapsed_ms >= self.conversion_delay_us(osr).div_ceil(1000),
+            None => false,
+        }
+    }
+
+    /// Reads the raw 24-bit result of the pending conversion. Temperature results are cached so
+    /// that [`Ms5611::compensate_pressure`] can turn a later pressure result into a measurement.
+    pub fn read_result(&mut self) -> Result<u32, Ms5611Error<I::Error>> {
+        let (channel, _) = self.pending.ok_or(Ms5611Error::NoConversionPending)?;
+        let mut data = [0u8; 4];
+
+        self.interface
+            .command_read(Command::AdcRead.byte(), &mut data[1..4])
+            .map_err(Ms5611Error::Bus)?;
+        self.pending = None;
+
+        let raw = adc_value(data)?;
+        self.record_conversion(channel, raw)?;
+
+        Ok(raw)
+    }
+
+    /// Performs a conversion and returns the three ADC bytes exactly as they came off the bus, most
+    /// significant first per the datasheet. Nothing is validated or recorded, so this is only
+    /// meant for debugging the transport, e.g. checking whether an adapter swaps bytes.
+    pub fn read_adc_bytes<D: ConversionDelay>(
+        &mut self,
+        channel: ConvertChannel,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<[u8; 3], Ms5611Error<I::Error>> {
+        let mut data = [0u8; 3];
 
-        let mut crc_check = 0u16;
+        self.interface
+            .write_command(convert_command(channel, osr))
+            .map_err(Ms5611Error::Bus)?;
+        delay.delay_us(self.conversion_delay_us(osr));
+        self.interface
+            .command_read(Command::AdcRead.byte(), &mut data)
+            .map_err(Ms5611Error::Bus)?;
 
-        // This is the CRC scheme in the MS5611 AN520 (Application Note)
-        fn crc_accumulate_byte(crc_check: &mut u16, byte: u8) {
-            *crc_check ^= byte as u16;
-            for _ in 0..8 {
-                if (*crc_check & 0x8000) > 0 {
-                    *crc_check = (*crc_check << 1) ^ 0x3000;
-                } else {
-                    *crc_check = *crc_check << 1;
+        Ok(data)
+    }
+
+    /// Returns how many pressure conversions have been performed since the temperature used for
+    /// compensation was measured, or `None` if no temperature has been read yet. Useful for
+    /// refreshing the temperature every N pressure reads.
+    pub fn pressure_reads_since_temperature(&self) -> Option<u32> {
+        self.last_d2.map(|_| self.pressure_reads_since_temperature)
+    }
+
+    fn record_conversion(
+        &mut self,
+        channel: ConvertChannel,
+        raw: u32,
+    ) -> Result<(), Ms5611Error<I::Error>> {
+        match channel {
+            ConvertChannel::Pressure => {
+                self.pressure_reads_since_temperature =
+                    self.pressure_reads_since_temperature.saturating_add(1);
+
+                let repeats = match self.last_d1 {
+                    Some((last, repeats)) if last == raw => repeats.saturating_add(1),
+                    _ => 1,
+                };
+                self.last_d1 = Some((raw, repeats));
+
+                if self
+                    .stuck_threshold
+                    .is_some_and(|threshold| repeats >= threshold.get())
+                {
+                    return Err(Ms5611Error::StuckReading);
                 }
             }
+            ConvertChannel::Temperature => {
+                self.last_d2 = Some(raw);
+                self.pressure_reads_since_temperature = 0;
+            }
         }
 
-        fn crc_accumulate_buf2(crc_check: &mut u16, buf: &[u8]) {
-            crc_accumulate_byte(crc_check,buf[0]);
-            crc_accumulate_byte(crc_check,buf[1]);
+        Ok(())
+    }
+
+    /// Compensates a raw pressure value (D1) with the most recent temperature conversion.
+    pub fn compensate_pressure(&self, d1: u32) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+        let d2 = self.last_d2.ok_or(Ms5611Error::TemperatureNotRead)?;
+
+        self.compensate_checked(&prom, d1, d2)
+            .map(|(measurement, _)| measurement)
+    }
+
+    /// Compensates with the configured variant and second-order setting, then applies the range
+    /// check if enabled.
+    fn compensate_checked(
+        &self,
+        prom: &Calibration,
+        d1: u32,
+        d2: u32,
+    ) -> Result<(Measurement, SecondOrderCompensation), Ms5611Error<I::Error>> {
+        let (measurement, details) =
+            compensate_with(prom, d1, d2, self.variant, self.second_order_compensation);
+
+        if self.range_check && !measurement.is_in_operating_range() {
+            return Err(Ms5611Error::OutOfRange(measurement));
         }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 0])?;
-        self.i2c.read(self.address, &mut buf)?;
-        crc_accumulate_buf2(&mut crc_check, &buf);
+        Ok((measurement, details))
+    }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 2])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.pressure_sensitivity = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+    /// Like `compensate_checked`, for a reading taken by the driver, so it also feeds the
+    /// pressure extremes.
+    fn compensate_tracked(
+        &mut self,
+        prom: &Calibration,
+        d1: u32,
+        d2: u32,
+    ) -> Result<(Measurement, SecondOrderCompensation), Ms5611Error<I::Error>> {
+        let (measurement, details) = self.compensate_checked(prom, d1, d2)?;
+        self.track_extremes(&measurement);
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 4])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.pressure_offset = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+        Ok((measurement, details))
+    }
+
+    fn track_extremes(&mut self, measurement: &Measurement) {
+        let pressure = measurement.pressure_mbar_x100;
+
+        self.pressure_extremes = Some(match self.pressure_extremes {
+            Some((min, max)) => (min.min(pressure), max.max(pressure)),
+            None => (pressure, pressure),
+        });
+    }
+
+    /// Lowest pressure returned by a read since the driver was created or
+    /// [`Ms5611::reset_extremes`] was called, in mbar * 100, or `None` before the first reading.
+    /// The minimum pressure is the highest altitude reached, so it turning around marks apogee.
+    ///
+    /// Only the value a read returns counts: the individual samples behind
+    /// [`Ms5611::read_median3`], the averages and [`Ms5611::read_filtered`] don't, so a spike one
+    /// of them rejects can't become the extreme. [`Ms5611::measure_noise`] doesn't count either.
+    ///
+    /// Neither does the non-blocking [`Ms5611::start_conversion`] / [`Ms5611::read_result`] /
+    /// [`Ms5611::compensate_pressure`] flow, since `compensate_pressure` only borrows the driver.
+    /// Applications using it have to track the extremes of its results themselves.
+    pub fn min_pressure(&self) -> Option<u32> {
+        self.pressure_extremes.map(|(min, _)| min)
+    }
+
+    /// Highest pressure returned by a read, see [`Ms5611::min_pressure`].
+    pub fn max_pressure(&self) -> Option<u32> {
+        self.pressure_extremes.map(|(_, max)| max)
+    }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 6])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.temp_coef_pressure_sensitivity = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+    /// Forgets the pressure extremes, e.g. when arming for a new flight.
+    pub fn reset_extremes(&mut self) {
+        self.pressure_extremes = None;
+    }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 8])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.temp_coef_pressure_offset = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+    /// Performs a pressure and a temperature conversion and returns the raw, uncompensated
+    /// `(D1, D2)` values. Doesn't require the PROM to be read.
+    ///
+    /// The temperature conversion is started in the same bus transaction that reads back the
+    /// pressure result, so a full read takes three transactions instead of four.
+    pub fn read_raw<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<(u32, u32), Ms5611Error<I::Error>> {
+        self.convert_both(osr, osr, delay)
+    }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 10])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.temp_ref = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+    pub fn read<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        self.read_with_osr(osr, osr, delay)
+    }
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 12])?;
-        self.i2c.read(self.address, &mut buf)?;
-        prom.temp_coef_temp = u16::from_be_bytes(buf);
-        crc_accumulate_buf2(&mut crc_check, &buf);
+    /// Like [`Ms5611::read`], but converts pressure and temperature at separate oversample ratios.
+    /// Temperature changes slowly, so e.g. pairing OSR 4096 pressure with OSR 256 temperature
+    /// saves ~8 ms per read for slightly noisier compensation. The compensation itself doesn't
+    /// depend on the ratios.
+    pub fn read_with_osr<D: ConversionDelay>(
+        &mut self,
+        pressure_osr: OversampleRatio,
+        temperature_osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let measurement = self.read_untracked(pressure_osr, temperature_osr, delay)?;
+        self.track_extremes(&measurement);
 
-        self.i2c.write(self.address, &[MS5611_PROM_READ + 14])?;
-        self.i2c.read(self.address, &mut buf)?;
-        // CRC is only last 4 bits
-        let crc = u16::from_be_bytes(buf) & 0xF;
-        crc_accumulate_byte(&mut crc_check, buf[0]);
-        crc_accumulate_byte(&mut crc_check, 0);
+        Ok(measurement)
+    }
+
+    /// [`Ms5611::read_with_osr`] without feeding the pressure extremes, for reads that combine
+    /// several samples into the value they return.
+    fn read_untracked<W: Wait<DELAY>>(
+        &mut self,
+        pressure_osr: OversampleRatio,
+        temperature_osr: OversampleRatio,
+        delay: &mut W,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
 
-        crc_check = crc_check >> 12;
+        let (d1, d2) = self.convert_both(pressure_osr, temperature_osr, delay)?;
 
-        if crc != crc_check {
-            panic!("PROM CRC did not match: {} != {}", crc, crc_check);
+        self.compensate_checked(&prom, d1, d2)
+            .map(|(measurement, _)| measurement)
+    }
+
+    /// Reads at the highest oversample ratio that fits `target_hz`, returning the ratio it chose
+    /// alongside the measurement. See [`Ms5611::oversample_ratio_for_rate`] for how it picks.
+    pub fn read_at_rate<D: ConversionDelay>(
+        &mut self,
+        target_hz: u32,
+        delay: &mut D,
+    ) -> Result<(OversampleRatio, Measurement), Ms5611Error<I::Error>> {
+        let osr = self.oversample_ratio_for_rate(target_hz);
+
+        self.read(osr, delay).map(|measurement| (osr, measurement))
+    }
+
+    /// Highest oversample ratio whose two conversions, pressure and temperature, fit in one period
+    /// of `target_hz`, including the configured delay margin. Falls back to OSR 256 when even that
+    /// is too slow, and returns OSR 4096 for a rate of 0. Bus transfer time isn't accounted for.
+    pub fn oversample_ratio_for_rate(&self, target_hz: u32) -> OversampleRatio {
+        use OversampleRatio::*;
+
+        if target_hz == 0 {
+            return Osr4096;
+        }
+        let budget_us = 1_000_000 / target_hz;
+
+        [Osr4096, Osr2048, Osr1024, Osr512]
+            .into_iter()
+            .find(|&osr| self.conversion_delay_us(osr).saturating_mul(2) <= budget_us)
+            .unwrap_or(Osr256)
+    }
+
+    /// Like [`Ms5611::read`], but returns the pressure in hectopascals and the temperature in
+    /// degrees Celsius directly. Both come from the same integer compensation, so they equal
+    /// [`Measurement::pressure_hpa`] and [`Measurement::temperature_celsius`] exactly.
+    #[cfg(feature = "float")]
+    pub fn read_f32<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<(f32, f32), Ms5611Error<I::Error>> {
+        let measurement = self.read(osr, delay)?;
+
+        Ok((
+            measurement.pressure_hpa(),
+            measurement.temperature_celsius(),
+        ))
+    }
+
+    /// Like [`Ms5611::read`], but also reports which second-order temperature corrections fired.
+    /// Both flags stay unset when second-order compensation is disabled.
+    pub fn read_detailed<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<(Measurement, SecondOrderCompensation), Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let (d1, d2) = self.read_raw(osr, delay)?;
+
+        self.compensate_tracked(&prom, d1, d2)
+    }
+
+    /// Like [`Ms5611::read`], but converts temperature first and returns
+    /// [`Ms5611Error::TemperatureOutOfBand`] without converting pressure if it's outside the band
+    /// set with [`Ms5611::set_temperature_band`]. Saves the pressure conversion time when reacting
+    /// to an over-temperature alarm. The band check uses the first-order temperature.
+    pub fn read_temperature_first<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let d2 = self.read_temperature_raw(osr, delay)?;
+        let (_, temperature) = first_order_temperature(&prom, d2);
+        let (min, max) = self.temperature_band;
+        if !(min..=max).contains(&temperature) {
+            return Err(Ms5611Error::TemperatureOutOfBand(temperature));
         }
 
-        self.prom = Some(prom);
+        let d1 = self.read_pressure_raw(osr, delay)?;
+
+        self.compensate_tracked(&prom, d1, d2)
+            .map(|(measurement, _)| measurement)
+    }
+
+    /// Like [`Ms5611::read`], but keeps trying when a conversion isn't done by the time its
+    /// result is read back, e.g. because the delay provider runs short. Makes up to
+    /// `max_attempts` attempts per conversion. Each one starts a fresh conversion and waits the
+    /// conversion time, margin included, plus `step_us` microseconds for every attempt before it.
+    /// Fails with [`Ms5611Error::ConversionNotReady`] if every attempt reads back zero.
+    ///
+    /// The MS5611 has no status register, so the ADC can't be polled. Per the datasheet, an ADC
+    /// read during a conversion returns zero but doesn't stop it, and it makes the final result
+    /// wrong. A repeated ADC read also returns zero. So every ADC read here comes only after the
+    /// full conversion time, and a zero result restarts the conversion instead of reading again.
+    pub fn read_polled<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        max_attempts: u32,
+        step_us: u32,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let d1 =
+            self.convert_polled(ConvertChannel::Pressure, osr, max_attempts, step_us, delay)?;
+        self.record_conversion(ConvertChannel::Pressure, d1)?;
+        let d2 = self.convert_polled(
+            ConvertChannel::Temperature,
+            osr,
+            max_attempts,
+            step_us,
+            delay,
+        )?;
+        self.record_conversion(ConvertChannel::Temperature, d2)?;
+
+        self.compensate_tracked(&prom, d1, d2)
+            .map(|(measurement, _)| measurement)
+    }
+
+    /// Like [`Ms5611::read`], but also returns the raw D1 and D2 values and the dT intermediate.
+    pub fn read_diagnostic<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<DiagnosticMeasurement, Ms5611Error<I::Error>> {
+        let prom = self.prom.ok_or(Ms5611Error::PromNotRead)?;
+
+        let (d1, d2) = self.read_raw(osr, delay)?;
+        let (measurement, _) = self.compensate_tracked(&prom, d1, d2)?;
+
+        Ok(DiagnosticMeasurement {
+            d1,
+            d2,
+            // D2 is 24 bits and C5 16 bits, so dT always fits
+            dt: first_order_temperature(&prom, d2).0 as i32,
+            measurement,
+        })
+    }
+
+    /// Like [`Ms5611::read`], but writes the result into `measurement` instead of returning it.
+    /// `measurement` is left untouched if the read fails.
+    ///
+    /// Neither this nor `read` allocates; the only per-conversion scratch space is a 4-byte stack
+    /// buffer for the ADC result.
+    pub fn read_into<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+        measurement: &mut Measurement,
+    ) -> Result<(), Ms5611Error<I::Error>> {
+        *measurement = self.read(osr, delay)?;
 
         Ok(())
     }
 
-    pub fn read<F: Fn(u32)>(&mut self, osr: OversampleRatio, delay_fn: F) -> Result<(u32, i32), I2C::Error> {
+    /// Takes `N` consecutive measurements, as fast as the conversions allow, and returns them in
+    /// order. The array lives on the stack, so mind its size (8 bytes per measurement) for large
+    /// `N`.
+    pub fn read_burst<const N: usize, D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<[Measurement; N], Ms5611Error<I::Error>> {
+        let mut measurements = [Measurement::default(); N];
+        for measurement in &mut measurements {
+            *measurement = self.read(osr, delay)?;
+        }
+
+        Ok(measurements)
+    }
+
+    /// Takes `samples` consecutive measurements and returns their mean. A sample count of 0 is
+    /// treated as 1.
+    pub fn read_averaged<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        samples: u16,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let samples = samples.max(1);
+        // A u16 count of u32/i32 values can't overflow an i64 sum
+        let mut pressure_sum = 0i64;
+        let mut temperature_sum = 0i64;
+
+        for _ in 0..samples {
+            let measurement = self.read_untracked(osr, osr, delay)?;
+            pressure_sum += measurement.pressure_mbar_x100 as i64;
+            temperature_sum += measurement.temperature_c_x100 as i64;
+        }
+
+        let measurement = Measurement {
+            pressure_mbar_x100: (pressure_sum / samples as i64) as u32,
+            temperature_c_x100: (temperature_sum / samples as i64) as i32,
+        };
+        self.track_extremes(&measurement);
+
+        Ok(measurement)
+    }
+
+    /// Like [`Ms5611::read_averaged`], but performs a single temperature conversion up front and
+    /// only averages pressure, saving a conversion per sample.
+    pub fn read_averaged_pressure<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        samples: u16,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        if self.prom.is_none() {
+            return Err(Ms5611Error::PromNotRead);
+        }
+
+        let samples = samples.max(1);
+        let mut pressure_sum = 0i64;
+        let mut temperature_c_x100 = 0;
+
+        self.read_temperature_raw(osr, delay)?;
+
+        for _ in 0..samples {
+            let measurement = self.read_pressure_untracked(osr, delay)?;
+            pressure_sum += measurement.pressure_mbar_x100 as i64;
+            temperature_c_x100 = measurement.temperature_c_x100;
+        }
+
+        let measurement = Measurement {
+            pressure_mbar_x100: (pressure_sum / samples as i64) as u32,
+            temperature_c_x100,
+        };
+        self.track_extremes(&measurement);
+
+        Ok(measurement)
+    }
+
+    /// Performs one temperature conversion and three pressure conversions and returns the median
+    /// pressure, which rejects a single spiked sample without the lag of a long average.
+    pub fn read_median3<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
         if self.prom.is_none() {
-            return Ok((0_u32, 0_i32));
+            return Err(Ms5611Error::PromNotRead);
+        }
+
+        self.read_temperature_raw(osr, delay)?;
+
+        let mut samples = [Measurement::default(); 3];
+        for sample in &mut samples {
+            *sample = self.read_pressure_untracked(osr, delay)?;
+        }
+        samples.sort_unstable_by_key(|sample| sample.pressure_mbar_x100);
+        self.track_extremes(&samples[1]);
+
+        Ok(samples[1])
+    }
+
+    /// Takes `samples` consecutive measurements and returns the population standard deviation of
+    /// pressure (mbar * 100) and temperature (degrees Celsius * 100), rounded down. Useful for
+    /// characterizing the noise of a sensor at rest. Fewer than two samples give 0.
+    pub fn measure_noise<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        samples: u16,
+        delay: &mut D,
+    ) -> Result<(u32, u32), Ms5611Error<I::Error>> {
+        let samples = samples.max(1);
+        // n * (sum of squares) overflows an i64 for large sample counts, so accumulate in i128
+        let mut pressure = (0i128, 0i128);
+        let mut temperature = (0i128, 0i128);
+
+        for _ in 0..samples {
+            let measurement = self.read_untracked(osr, osr, delay)?;
+            let p = measurement.pressure_mbar_x100 as i128;
+            let t = measurement.temperature_c_x100 as i128;
+            pressure = (pressure.0 + p, pressure.1 + p * p);
+            temperature = (temperature.0 + t, temperature.1 + t * t);
+        }
+
+        let std = |(sum, sum_squares): (i128, i128)| {
+            let n = samples as i128;
+            let variance = (n * sum_squares - sum * sum) / (n * n);
+            (variance.max(0) as u128).isqrt() as u32
+        };
+
+        Ok((std(pressure), std(temperature)))
+    }
+
+    /// Takes a measurement and blends it into a running exponential moving average, returning the
+    /// filtered value. `alpha` is the weight of the new sample in 1/256ths: small values smooth
+    /// heavily, 255 follows the raw readings almost exactly. The first call after construction or
+    /// [`Ms5611::reset_filter`] initializes the average to that sample.
+    pub fn read_filtered<D: ConversionDelay>(
+        &mut self,
+        osr: OversampleRatio,
+        alpha: u8,
+        delay: &mut D,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let measurement = self.read_untracked(osr, osr, delay)?;
+        let pressure = (measurement.pressure_mbar_x100 as i64) << 8;
+        let temperature = (measurement.temperature_c_x100 as i64) << 8;
+
+        let (pressure, temperature) = match self.filter {
+            Some((avg_pressure, avg_temperature)) => (
+                avg_pressure + (((pressure - avg_pressure) * alpha as i64) >> 8),
+                avg_temperature + (((temperature - avg_temperature) * alpha as i64) >> 8),
+            ),
+            None => (pressure, temperature),
+        };
+        self.filter = Some((pressure, temperature));
+
+        let measurement = Measurement {
+            pressure_mbar_x100: (pressure >> 8) as u32,
+            temperature_c_x100: (temperature >> 8) as i32,
+        };
+        self.track_extremes(&measurement);
+
+        Ok(measurement)
+    }
+
+    /// Discards the moving average so the next [`Ms5611::read_filtered`] starts over.
+    pub fn reset_filter(&mut self) {
+        self.filter = None;
+    }
+
+    fn convert<D: ConversionDelay>(
+        &mut self,
+        channel: ConvertChannel,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<u32, Ms5611Error<I::Error>> {
+        self.retry_bus_errors(|ms5611| ms5611.convert_once(channel, osr, delay))
+    }
+
+    /// Runs `f`, repeating it up to the configured number of ADC retries while it fails on the
+    /// bus.
+    fn retry_bus_errors<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, Ms5611Error<I::Error>>,
+    ) -> Result<T, Ms5611Error<I::Error>> {
+        for _ in 0..self.adc_retries {
+            match f(self) {
+                Err(Ms5611Error::Bus(_)) => {}
+                result => return result,
+            }
         }
 
+        f(self)
+    }
+
+    fn convert_both<W: Wait<DELAY>>(
+        &mut self,
+        pressure_osr: OversampleRatio,
+        temperature_osr: OversampleRatio,
+        delay: &mut W,
+    ) -> Result<(u32, u32), Ms5611Error<I::Error>> {
+        let (d1, d2) = self.retry_bus_errors(|ms5611| {
+            ms5611.convert_both_once(pressure_osr, temperature_osr, delay)
+        })?;
+        self.record_conversion(ConvertChannel::Pressure, d1)?;
+        self.record_conversion(ConvertChannel::Temperature, d2)?;
+
+        Ok((d1, d2))
+    }
+
+    /// Converts pressure then temperature, starting the temperature conversion in the same bus
+    /// transaction that reads back the pressure result.
+    fn convert_both_once<W: Wait<DELAY>>(
+        &mut self,
+        pressure_osr: OversampleRatio,
+        temperature_osr: OversampleRatio,
+        delay: &mut W,
+    ) -> Result<(u32, u32), Ms5611Error<I::Error>> {
+        let mut d1 = [0u8; 4];
+        let mut d2 = [0u8; 4];
+
+        self.interface
+            .write_command(convert_command(ConvertChannel::Pressure, pressure_osr))
+            .map_err(Ms5611Error::Bus)?;
+        delay.wait_us(self.conversion_delay_us(pressure_osr), &mut self.delay);
+        self.interface
+            .command_read_then_command(
+                Command::AdcRead.byte(),
+                &mut d1[1..4],
+                convert_command(ConvertChannel::Temperature, temperature_osr),
+            )
+            .map_err(Ms5611Error::Bus)?;
+        delay.wait_us(self.conversion_delay_us(temperature_osr), &mut self.delay);
+        self.interface
+            .command_read(Command::AdcRead.byte(), &mut d2[1..4])
+            .map_err(Ms5611Error::Bus)?;
+
+        Ok((adc_value(d1)?, adc_value(d2)?))
+    }
+
+    fn convert_once<D: ConversionDelay>(
+        &mut self,
+        channel: ConvertChannel,
+        osr: OversampleRatio,
+        delay: &mut D,
+    ) -> Result<u32, Ms5611Error<I::Error>> {
+        let [high, mid, low] = self.read_adc_bytes(channel, osr, delay)?;
+
+        adc_value([0, high, mid, low])
+    }
+
+    fn convert_polled<D: ConversionDelay>(
+        &mut self,
+        channel: ConvertChannel,
+        osr: OversampleRatio,
+        max_attempts: u32,
+        step_us: u32,
+        delay: &mut D,
+    ) -> Result<u32, Ms5611Error<I::Error>> {
         let mut data = [0u8; 4];
 
-        self.i2c.write(self.address, &[osr as u8])?;
-        delay_fn(osr.delay_ms());
-        self.i2c.write(self.address, &[MS5611_READ_ADC])?;
-        self.i2c.read(self.address, &mut data[1..4])?;
-        let d1 = i32::from_be_bytes(data);
+        for attempt in 0..max_attempts {
+            self.interface
+                .write_command(convert_command(channel, osr))
+                .map_err(Ms5611Error::Bus)?;
+            delay.delay_us(
+                self.conversion_delay_us(osr)
+                    .saturating_add(step_us.saturating_mul(attempt)),
+            );
+            self.interface
+                .command_read(Command::AdcRead.byte(), &mut data[1..4])
+                .map_err(Ms5611Error::Bus)?;
+
+            match adc_value(data) {
+                Err(Ms5611Error::ConversionNotReady) => continue,
+                result => return result,
+            }
+        }
+
+        Err(Ms5611Error::ConversionNotReady)
+    }
+}
+
+impl<I: Interface, DELAY: ConversionDelay> Ms5611<I, DELAY> {
+    /// Reads using the oversample ratio and delay provider configured through
+    /// [`Ms5611Builder`].
+    pub fn read_configured(&mut self) -> Result<Measurement, Ms5611Error<I::Error>> {
+        self.read_with(self.osr)
+    }
+
+    /// Reads using the configured delay provider, overriding the configured oversample ratio.
+    pub fn read_with(
+        &mut self,
+        osr: OversampleRatio,
+    ) -> Result<Measurement, Ms5611Error<I::Error>> {
+        let measurement = self.read_untracked(osr, osr, &mut StoredDelay)?;
+        self.track_extremes(&measurement);
+
+        Ok(measurement)
+    }
+}
+
+/// Datasheet conversion time at `osr` lengthened by `margin_percent`, in microseconds.
+fn conversion_delay_us(osr: OversampleRatio, margin_percent: u32) -> u32 {
+    let delay_us = osr.delay_us() as u64 * (100 + margin_percent as u64) / 100;
+
+    delay_us.min(u32::MAX as u64) as u32
+}
+
+/// Decodes a 24-bit ADC read (in the low three bytes of `data`), rejecting invalid values.
+fn adc_value<E>(data: [u8; 4]) -> Result<u32, Ms5611Error<E>> {
+    match u32::from_be_bytes(data) {
+        0 => Err(Ms5611Error::ConversionNotReady),
+        0x00FF_FFFF => Err(Ms5611Error::AdcSaturated),
+        value => Ok(value),
+    }
+}
+
+fn prom_words_from_bytes(bytes: &[u8; 16]) -> [u16; 8] {
+    core::array::from_fn(|index| u16::from_be_bytes([bytes[index * 2], bytes[index * 2 + 1]]))
+}
+
+/// Computes the 4-bit PROM CRC over all eight raw PROM words, following the algorithm in
+/// application note AN520. The low byte of word 7, which holds the stored CRC, is treated as zero,
+/// so `prom` can be passed exactly as read from the sensor.
+pub fn crc4(prom: &[u16; 8]) -> u8 {
+    fn crc_accumulate_byte(crc_check: &mut u16, byte: u8) {
+        *crc_check ^= byte as u16;
+        for _ in 0..8 {
+            if (*crc_check & 0x8000) > 0 {
+                *crc_check = (*crc_check << 1) ^ 0x3000;
+            } else {
+                *crc_check <<= 1;
+            }
+        }
+    }
+
+    let mut crc_check = 0u16;
+
+    for (index, word) in prom.iter().enumerate() {
+        let [high, low] = word.to_be_bytes();
+        crc_accumulate_byte(&mut crc_check, high);
+        // The low byte of the last word holds the CRC itself and is zeroed out
+        crc_accumulate_byte(&mut crc_check, if index == 7 { 0 } else { low });
+    }
+
+    (crc_check >> 12) as u8
+}
+
+/// Checks the CRC nibble stored in word 7 against the eight raw PROM words, e.g. to re-verify
+/// PROM contents persisted to flash.
+pub fn verify_prom_crc(words: &[u16; 8]) -> bool {
+    u16::from(crc4(words)) == words[7] & 0xF
+}
+
+/// Returns true if the coefficient words 1 to 6 are all 0x0000 or all 0xFFFF.
+fn coefficients_blank(words: &[u16]) -> bool {
+    let coefficients = &words[1..=6];
+
+    coefficients.iter().all(|&word| word == 0x0000)
+        || coefficients.iter().all(|&word| word == 0xFFFF)
+}
+
+fn calibration_from_prom<E>(prom: &[u16; 8]) -> Result<Calibration, Ms5611Error<E>> {
+    if coefficients_blank(prom) {
+        return Err(Ms5611Error::InvalidCalibration);
+    }
+
+    // CRC is only last 4 bits
+    let crc = prom[7] & 0xF;
+    let crc_check = u16::from(crc4(prom));
+
+    if crc != crc_check {
+        return Err(Ms5611Error::CrcMismatch {
+            expected: crc,
+            computed: crc_check,
+        });
+    }
+
+    Ok(calibration_from_words(prom))
+}
+
+/// Builds the calibration from PROM words 1 to 6, ignoring the rest.
+fn calibration_from_words(prom: &[u16]) -> Calibration {
+    Calibration {
+        pressure_sensitivity: prom[1],
+        pressure_offset: prom[2],
+        temp_coef_pressure_sensitivity: prom[3],
+        temp_coef_pressure_offset: prom[4],
+        temp_ref: prom[5],
+        temp_coef_temp: prom[6],
+    }
+}
+
+/// Returns the difference to the reference temperature (dT) and the temperature in Celsius * 100.
+fn first_order_temperature(prom: &Calibration, d2: u32) -> (i64, i32) {
+    let dt = (d2 as i64) - ((prom.temp_ref as i64) << 8);
+
+    // Units: Celcius * 100. With 24-bit D2 and 16-bit coefficients this stays within about
+    // +-2^17, so the narrowing can't truncate
+    let temperature = 2000 + (((dt * (prom.temp_coef_temp as i64)) >> 23) as i32);
+
+    (dt, temperature)
+}
+
+/// Second-order temperature correction T2, in degrees Celsius * 100, for the first-order `dt`
+/// and `temperature`. The same for both variants, and 0 from 20 °C up.
+fn second_order_temperature(dt: i64, temperature: i32) -> i32 {
+    if temperature < 2000 {
+        // dT is within about +-2^24, so dT^2 / 2^31 stays far below i32::MAX
+        ((dt * dt) >> 31) as i32
+    } else {
+        0
+    }
+}
+
+/// Compensates raw D1 (pressure) and D2 (temperature) values with the given calibration, using
+/// the exact math of [`Ms5611::read`] for an MS5611 with second-order compensation enabled, the
+/// driver's defaults. Useful for replaying recorded raw data offline.
+pub fn compensate(calibration: &Calibration, d1: u32, d2: u32) -> Measurement {
+    compensate_with(calibration, d1, d2, Variant::Ms5611, true).0
+}
+
+fn compensate_with(
+    prom: &Calibration,
+    d1: u32,
+    d2: u32,
+    variant: Variant,
+    second_order: bool,
+) -> (Measurement, SecondOrderCompensation) {
+    let (dt, temperature) = first_order_temperature(prom, d2);
+
+    let (mut offset, mut sens) = match variant {
+        Variant::Ms5611 => (
+            ((prom.pressure_offset as i64) << 16)
+                + (((prom.temp_coef_pressure_offset as i64) * dt) >> 7),
+            ((prom.pressure_sensitivity as i64) << 15)
+                + (((prom.temp_coef_pressure_sensitivity as i64) * dt) >> 8),
+        ),
+        Variant::Ms5607 => (
+            ((prom.pressure_offset as i64) << 17)
+                + (((prom.temp_coef_pressure_offset as i64) * dt) >> 6),
+            ((prom.pressure_sensitivity as i64) << 16)
+                + (((prom.temp_coef_pressure_sensitivity as i64) * dt) >> 7),
+        ),
+    };
+
+    let mut t2 = 0;
+    let mut off2 = 0;
+    let mut sens2 = 0;
+    let low_temperature = second_order && temperature < 2000;
+    let very_low_temperature = second_order && temperature < -1500;
+
+    // Low temperature (< 20C)
+    if low_temperature {
+        t2 = second_order_temperature(dt, temperature);
+        let low = (temperature as i64 - 2000).pow(2);
+        match variant {
+            Variant::Ms5611 => {
+                off2 = (5 * low) >> 1;
+                sens2 = off2 >> 1;
+            }
+            Variant::Ms5607 => {
+                off2 = (61 * low) >> 4;
+                sens2 = 2 * low;
+            }
+        }
+    }
+
+    // Very low temperature (< -15)
+    if very_low_temperature {
+        let very_low = (temperature as i64 + 1500).pow(2);
+        match variant {
+            Variant::Ms5611 => {
+                off2 += 7 * very_low;
+                sens2 += (11 * very_low) >> 1;
+            }
+            Variant::Ms5607 => {
+                off2 += 15 * very_low;
+                sens2 += 8 * very_low;
+            }
+        }
+    }
+
+    offset -= off2;
+    sens -= sens2;
+
+    // Everything above is computed in i64, and for any 24-bit D1/D2 and 16-bit coefficients the
+    // largest intermediate (D1 * SENS) stays below 2^62. The result can still leave the range of
+    // a real pressure for nonsensical inputs, so clamp it instead of letting a negative value wrap.
+    // Units: mbar * 100
+    let pressure = ((((d1 as i64) * sens) >> 21) - offset) >> 15;
+
+    let measurement = Measurement {
+        pressure_mbar_x100: pressure.clamp(0, u32::MAX as i64) as u32,
+        temperature_c_x100: temperature - t2,
+    };
+    let details = SecondOrderCompensation {
+        low_temperature,
+        very_low_temperature,
+        offset: off2,
+        sensitivity: sens2,
+        temperature: t2,
+    };
+
+    (measurement, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Worked example from the MS5611-01BA03 datasheet
+    const MS5611_CALIBRATION: Calibration = Calibration {
+        pressure_sensitivity: 40127,
+        pressure_offset: 36924,
+        temp_coef_pressure_sensitivity: 23317,
+        temp_coef_pressure_offset: 23282,
+        temp_ref: 33464,
+        temp_coef_temp: 28312,
+    };
+
+    #[test]
+    fn ms5611_datasheet_example() {
+        let measurement = compensate(&MS5611_CALIBRATION, 9_085_466, 8_569_150);
+
+        assert_eq!(measurement.temperature_c_x100, 2007);
+        assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    }
+
+    #[test]
+    fn ms5607_datasheet_example() {
+        // Worked example from the MS5607-02BA03 datasheet, which only covers first order
+        let calibration = Calibration {
+            pressure_sensitivity: 46372,
+            pressure_offset: 43981,
+            temp_coef_pressure_sensitivity: 29059,
+            temp_coef_pressure_offset: 27842,
+            temp_ref: 31553,
+            temp_coef_temp: 28165,
+        };
+
+        let (measurement, _) =
+            compensate_with(&calibration, 6_465_444, 8_077_636, Variant::Ms5607, false);
+
+        assert_eq!(measurement.temperature_c_x100, 2000);
+        assert_eq!(measurement.pressure_mbar_x100, 110_002);
+    }
+
+    #[test]
+    fn second_order_compensation_when_cold() {
+        // First-order TEMP is exactly 0 °C here, so T2 = dT^2 / 2^31 = 163 and
+        // OFF2 = 5 * 2000^2 / 2
+        let (measurement, details) = compensate_with(
+            &MS5611_CALIBRATION,
+            9_085_466,
+            7_974_211,
+            Variant::Ms5611,
+            true,
+        );
 
-        self.i2c.write(self.address, &[(osr as u8) + 0x10])?;
-        delay_fn(osr.delay_ms());
-        self.i2c.write(self.address, &[MS5611_READ_ADC])?;
-        self.i2c.read(self.address, &mut data[1..4])?;
-        let d2: i64 = i32::from_be_bytes(data) as i64;
+        assert_eq!(measurement.temperature_c_x100, -163);
+        assert_eq!(measurement.pressure_mbar_x100, 95_791);
+        assert_eq!(details.temperature, 163);
+        assert_eq!(details.offset, 10_000_000);
+        assert_eq!(details.sensitivity, 5_000_000);
+        assert!(!details.very_low_temperature);
 
-        let prom = self.prom.as_ref().unwrap();
+        let (measurement, details) = compensate_with(
+            &MS5611_CALIBRATION,
+            9_085_466,
+            7_400_000,
+            Variant::Ms5611,
+            true,
+        );
 
-        let dt = d2 - ((prom.temp_ref as i64) << 8);
+        assert_eq!(measurement.temperature_c_x100, -2571);
+        assert_eq!(measurement.pressure_mbar_x100, 90_941);
+        assert_eq!(details.temperature, 633);
+        assert!(details.very_low_temperature);
 
-        // Units: Celcius * 100
-        let temperature = 2000 + (((dt * (prom.temp_coef_temp as i64)) >> 23) as i32);
-        let mut offset = ((prom.pressure_offset as i64) << 16)
-            + (((prom.temp_coef_pressure_offset as i64) * dt) >> 7);
-        let mut sens = ((prom.pressure_sensitivity as i64) << 15)
-            + (((prom.temp_coef_pressure_sensitivity as i64) * dt) >> 8);
+        // T2 is the same for the MS5607, on the MS5607 datasheet coefficients
+        let calibration = Calibration {
+            pressure_sensitivity: 46372,
+            pressure_offset: 43981,
+            temp_coef_pressure_sensitivity: 29059,
+            temp_coef_pressure_offset: 27842,
+            temp_ref: 31553,
+            temp_coef_temp: 28165,
+        };
+        let (measurement, details) =
+            compensate_with(&calibration, 6_465_444, 7_377_568, Variant::Ms5607, true);
 
-        let mut off2 = 0;
-        let mut sens2 = 0;
+        assert_eq!(measurement.temperature_c_x100, -579);
+        assert_eq!(measurement.pressure_mbar_x100, 103_947);
+        assert_eq!(details.temperature, 228);
+    }
 
-        // Low temperature (< 20C)
-        if temperature < 2000 {
-            off2 = ((5 * (temperature - 2000).pow(2)) >> 1) as i64;
-            sens2 = off2 >> 1;
+    #[test]
+    fn oversample_ratio_byte_round_trip() {
+        for osr in [
+            OversampleRatio::Osr256,
+            OversampleRatio::Osr512,
+            OversampleRatio::Osr1024,
+            OversampleRatio::Osr2048,
+            OversampleRatio::Osr4096,
+        ] {
+            assert_eq!(OversampleRatio::try_from(u8::from(osr)), Ok(osr));
         }
 
-        // Very low temperature (< -15)
-        if temperature < -1500 {
-            off2 += 7 * (temperature as i64 + 1500).pow(2);
-            sens2 += ((11 * (temperature as i64 + 1500).pow(2)) >> 1) as i64;
+        assert_eq!(
+            OversampleRatio::try_from(0x41),
+            Err(InvalidOversampleRatio(0x41))
+        );
+    }
+
+    #[test]
+    fn crc4_matches_an520_example() {
+        // Example PROM contents from AN520
+        let mut prom = [
+            0x3132, 0x3334, 0x3536, 0x3738, 0x3940, 0x4142, 0x4344, 0x4500,
+        ];
+        assert_eq!(crc4(&prom), 0xB);
+
+        // The stored CRC nibble doesn't feed into its own computation
+        prom[7] |= 0xB;
+        assert_eq!(crc4(&prom), 0xB);
+        assert!(verify_prom_crc(&prom));
+    }
+
+    #[test]
+    fn measurement_display() {
+        let measurement = Measurement {
+            pressure_mbar_x100: 101_325,
+            temperature_c_x100: 2137,
+        };
+        assert_eq!(measurement.to_string(), "1013.25 hPa, 21.37 °C");
+
+        let cold = Measurement {
+            pressure_mbar_x100: 100_001,
+            temperature_c_x100: -5,
+        };
+        assert_eq!(cold.to_string(), "1000.01 hPa, -0.05 °C");
+    }
+
+    #[test]
+    fn drivers_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        // With trivially Send + Sync parameters, only the driver's own state is checked
+        assert_send_sync::<Ms5611<(), ()>>();
+        assert_send_sync::<Ms5611<(), FnDelay>>();
+        assert_send_sync::<Ms5611Builder<()>>();
+        #[cfg(feature = "i2c")]
+        assert_send_sync::<I2cInterface<()>>();
+        #[cfg(feature = "spi")]
+        assert_send_sync::<SpiInterface<(), ()>>();
+        #[cfg(feature = "async")]
+        assert_send_sync::<Ms5611Async<()>>();
+    }
+
+    #[test]
+    fn operating_range() {
+        let inside = Measurement {
+            pressure_mbar_x100: 100_009,
+            temperature_c_x100: 2007,
+        };
+        assert!(inside.is_in_operating_range());
+
+        assert!(!Measurement {
+            pressure_mbar_x100: Measurement::MAX_PRESSURE_MBAR_X100 + 1,
+            ..inside
+        }
+        .is_in_operating_range());
+        assert!(!Measurement {
+            temperature_c_x100: Measurement::MIN_TEMPERATURE_C_X100 - 1,
+            ..inside
         }
+        .is_in_operating_range());
+    }
 
-        offset -= off2;
-        sens -= sens2;
+    #[test]
+    fn extreme_inputs_do_not_overflow() {
+        let coldest = Calibration {
+            temp_ref: u16::MAX,
+            temp_coef_temp: u16::MAX,
+            ..MS5611_CALIBRATION
+        };
+        let extreme = Calibration {
+            pressure_sensitivity: u16::MAX,
+            pressure_offset: u16::MAX,
+            temp_coef_pressure_sensitivity: u16::MAX,
+            temp_coef_pressure_offset: u16::MAX,
+            temp_ref: 0,
+            temp_coef_temp: u16::MAX,
+        };
 
-        // Units: mbar * 100
-        let pressure: i32 = (((((d1 as i64) * sens) >> 21) - offset) >> 15) as i32;
+        for variant in [Variant::Ms5611, Variant::Ms5607] {
+            // Coldest reading the ADC can produce, which runs through both second-order branches
+            let (measurement, details) = compensate_with(&coldest, 9_085_466, 1, variant, true);
+            assert!(measurement.temperature_c_x100 < -1500);
+            assert_eq!(measurement.pressure_mbar_x100, 0);
+            assert!(details.low_temperature && details.very_low_temperature);
 
-        Ok((pressure as u32, temperature))
+            for d1 in [1, 0x00FF_FFFE] {
+                for d2 in [1, 0x00FF_FFFE] {
+                    compensate_with(&coldest, d1, d2, variant, true);
+                    compensate_with(&extreme, d1, d2, variant, true);
+                }
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,37 @@
+use crate::Measurement;
+
+/// Result of combining the readings of two redundant sensors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RedundantMeasurement {
+    /// Mean pressure and temperature of both sensors.
+    pub measurement: Measurement,
+    /// Absolute pressure difference between the sensors, in mbar * 100.
+    pub pressure_difference: u32,
+    /// Set when `pressure_difference` exceeds the allowed threshold, which usually means one of
+    /// the sensors has failed.
+    pub disagreement: bool,
+}
+
+/// Averages the readings of two sensors and flags them as disagreeing when their pressures are
+/// more than `max_difference_mbar_x100` apart.
+pub fn combine_redundant(
+    a: &Measurement,
+    b: &Measurement,
+    max_difference_mbar_x100: u32,
+) -> RedundantMeasurement {
+    let pressure_difference = a.pressure_mbar_x100.abs_diff(b.pressure_mbar_x100);
+
+    // Widened so the sums can't overflow
+    let pressure = (a.pressure_mbar_x100 as u64 + b.pressure_mbar_x100 as u64) / 2;
+    let temperature = (a.temperature_c_x100 as i64 + b.temperature_c_x100 as i64) / 2;
+
+    RedundantMeasurement {
+        measurement: Measurement {
+            pressure_mbar_x100: pressure as u32,
+            temperature_c_x100: temperature as i32,
+        },
+        pressure_difference,
+        disagreement: pressure_difference > max_difference_mbar_x100,
+    }
+}
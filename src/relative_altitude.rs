@@ -0,0 +1,51 @@
+use crate::Measurement;
+
+/// Altitude of `pressure_pa` relative to `reference_pa` in meters, positive above the reference,
+/// from a linear approximation of the `float` feature's `altitude_m`. Only needs `f32`
+/// arithmetic, so it is available without `libm`.
+///
+/// Uses the sea-level slope of the barometric formula, about 8.3 m per hPa, scaled by the mean
+/// of the two pressures. Near sea level it stays within 0.5 m of the full formula for offsets up
+/// to ±100 m. The slope is ~2.5% too steep per km of reference elevation, and the error grows
+/// quadratically with the offset, so re-reference rather than using it over larger changes.
+pub fn relative_altitude_m(pressure_pa: f32, reference_pa: f32) -> f32 {
+    let mean_pa = (pressure_pa + reference_pa) / 2.0;
+
+    44_330.0 / 5.255 * (reference_pa - pressure_pa) / mean_pa
+}
+
+impl Measurement {
+    /// Altitude in meters relative to a reference pressure in pascals, such as the one at
+    /// takeoff, see [`relative_altitude_m`] for the approximation and its range.
+    pub fn relative_altitude_m(&self, reference_pa: f32) -> f32 {
+        relative_altitude_m(self.pressure_mbar_x100 as f32, reference_pa)
+    }
+}
+
+// The comparison needs the barometric formula from the `float` feature
+#[cfg(all(test, feature = "float"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_altitude_matches_barometric_formula() {
+        use crate::{altitude_m, STANDARD_SEA_LEVEL_PA};
+
+        for reference_pa in [STANDARD_SEA_LEVEL_PA, 100_000.0] {
+            let reference_m = altitude_m(reference_pa, STANDARD_SEA_LEVEL_PA);
+
+            for offset_m in (-100..=100).step_by(5) {
+                let altitude = reference_m + offset_m as f32;
+                let pressure_pa =
+                    STANDARD_SEA_LEVEL_PA * libm::powf(1.0 - altitude / 44_330.0, 5.255);
+                let expected_m = altitude_m(pressure_pa, STANDARD_SEA_LEVEL_PA) - reference_m;
+                let actual_m = relative_altitude_m(pressure_pa, reference_pa);
+
+                assert!(
+                    (actual_m - expected_m).abs() < 0.5,
+                    "{offset_m} m: {actual_m} m vs {expected_m} m"
+                );
+            }
+        }
+    }
+}
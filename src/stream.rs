@@ -0,0 +1,35 @@
+use crate::{ConversionDelay, Interface, Measurement, Ms5611, Ms5611Error, OversampleRatio};
+
+/// Endless stream of measurements, created by [`Ms5611::measurements`].
+///
+/// Each call to `next` performs a full pressure and temperature read, so the iterator never
+/// returns `None`. Use `take` to capture a fixed number of readings.
+#[derive(Debug)]
+pub struct Measurements<'a, I, DELAY, D> {
+    ms5611: &'a mut Ms5611<I, DELAY>,
+    osr: OversampleRatio,
+    delay: &'a mut D,
+}
+
+impl<I: Interface, DELAY> Ms5611<I, DELAY> {
+    /// Returns an iterator that yields a fresh [`Ms5611::read`] at `osr` on every `next`.
+    pub fn measurements<'a, D: ConversionDelay>(
+        &'a mut self,
+        osr: OversampleRatio,
+        delay: &'a mut D,
+    ) -> Measurements<'a, I, DELAY, D> {
+        Measurements {
+            ms5611: self,
+            osr,
+            delay,
+        }
+    }
+}
+
+impl<I: Interface, DELAY, D: ConversionDelay> Iterator for Measurements<'_, I, DELAY, D> {
+    type Item = Result<Measurement, Ms5611Error<I::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.ms5611.read(self.osr, self.delay))
+    }
+}
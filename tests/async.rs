@@ -0,0 +1,136 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use embedded_hal_mock::eh1::delay::{CheckedDelay, NoopDelay, Transaction as DelayTransaction};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use ms5611_rs::{verify_prom_crc, Ms5611Async, Ms5611Error, OversampleRatio};
+
+const ADDRESS: u8 = 0x77;
+
+/// Datasheet example ADC results, D1 = 9085466 and D2 = 8569150.
+const D1: [u8; 3] = [0x8A, 0xA2, 0x1A];
+const D2: [u8; 3] = [0x82, 0xC1, 0x3E];
+
+/// The mocks complete immediately, so polling in a loop never actually spins.
+struct NoopWake;
+
+impl Wake for NoopWake {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWake));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Datasheet calibration coefficients, with the CRC nibble filled in.
+fn prom_words() -> [u16; 8] {
+    let mut words = [0, 40127, 36924, 23317, 23282, 33464, 28312, 0];
+    words[7] = (0..16)
+        .find(|&crc| {
+            words[7] = crc;
+            verify_prom_crc(&words)
+        })
+        .expect("one of the sixteen nibbles must match");
+
+    words
+}
+
+fn prom_transactions(address: u8, words: &[u16; 8]) -> Vec<Transaction> {
+    let mut transactions = vec![Transaction::transaction_start(address)];
+    for (index, word) in (0u8..).zip(words) {
+        transactions.push(Transaction::write(address, vec![0xA0 + index * 2]));
+        transactions.push(Transaction::read(address, word.to_be_bytes().to_vec()));
+    }
+    transactions.push(Transaction::transaction_end(address));
+
+    transactions
+}
+
+/// Same script as the blocking driver's read at OSR 4096.
+fn read_transactions(address: u8, d1: [u8; 3], d2: [u8; 3]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(address, vec![0x48]),
+        Transaction::transaction_start(address),
+        Transaction::write(address, vec![0x00]),
+        Transaction::read(address, d1.to_vec()),
+        Transaction::write(address, vec![0x58]),
+        Transaction::transaction_end(address),
+        Transaction::write_read(address, vec![0x00], d2.to_vec()),
+    ]
+}
+
+#[test]
+fn init_and_read() {
+    let mut expectations = vec![Transaction::write(ADDRESS, vec![0x1E])];
+    expectations.extend(prom_transactions(ADDRESS, &prom_words()));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611Async::new(Mock::new(&expectations), ADDRESS);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::async_delay_ms(3),
+        DelayTransaction::async_delay_us(9040),
+        DelayTransaction::async_delay_us(9040),
+    ]);
+
+    block_on(ms5611.init(&mut delay)).unwrap();
+    let measurement = block_on(ms5611.read(OversampleRatio::Osr4096, &mut delay)).unwrap();
+
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(measurement.temperature_c_x100, 2007);
+
+    delay.done();
+    ms5611.release().done();
+}
+
+#[test]
+fn read_without_prom_fails() {
+    let mut ms5611 = Ms5611Async::new(Mock::new(&[]), ADDRESS);
+
+    assert_eq!(
+        block_on(ms5611.read(OversampleRatio::Osr4096, &mut NoopDelay::new())),
+        Err(Ms5611Error::PromNotRead)
+    );
+
+    ms5611.release().done();
+}
+
+#[test]
+fn measurements_wait_between_samples() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611Async::new(Mock::new(&expectations), ADDRESS);
+    block_on(ms5611.read_prom()).unwrap();
+
+    let mut conversion_delay = NoopDelay::new();
+    // The first sample is taken immediately, only the second waits out the period
+    let mut period_delay = CheckedDelay::new(&[DelayTransaction::async_delay_ms(250)]);
+    let mut measurements = ms5611.measurements(
+        OversampleRatio::Osr4096,
+        &mut conversion_delay,
+        &mut period_delay,
+        250,
+    );
+
+    for _ in 0..2 {
+        let measurement = block_on(measurements.next()).unwrap();
+        assert_eq!(measurement.pressure_mbar_x100, 100_009);
+        assert_eq!(measurement.temperature_c_x100, 2007);
+    }
+
+    period_delay.done();
+    ms5611.release().done();
+}
@@ -0,0 +1,734 @@
+#![cfg(feature = "i2c")]
+
+use core::cell::RefCell;
+use core::num::NonZeroU32;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_bus::i2c::RefCellDevice;
+use embedded_hal_mock::eh1::delay::{CheckedDelay, NoopDelay, Transaction as DelayTransaction};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use ms5611_rs::{
+    verify_prom_crc, ConvertChannel, Measurement, Ms5611, Ms5611Address, Ms5611Builder,
+    Ms5611Error, OversampleRatio,
+};
+
+const ADDRESS: u8 = 0x77;
+
+/// Datasheet example ADC results, D1 = 9085466 and D2 = 8569150.
+const D1: [u8; 3] = [0x8A, 0xA2, 0x1A];
+const D2: [u8; 3] = [0x82, 0xC1, 0x3E];
+
+/// Datasheet calibration coefficients, with the CRC nibble filled in.
+fn prom_words() -> [u16; 8] {
+    let mut words = [0, 40127, 36924, 23317, 23282, 33464, 28312, 0];
+    words[7] = (0..16)
+        .find(|&crc| {
+            words[7] = crc;
+            verify_prom_crc(&words)
+        })
+        .expect("one of the sixteen nibbles must match");
+
+    words
+}
+
+fn prom_transactions(address: u8, words: &[u16; 8]) -> Vec<Transaction> {
+    let mut transactions = vec![Transaction::transaction_start(address)];
+    for (index, word) in (0u8..).zip(words) {
+        transactions.push(Transaction::write(address, vec![0xA0 + index * 2]));
+        transactions.push(Transaction::read(address, word.to_be_bytes().to_vec()));
+    }
+    transactions.push(Transaction::transaction_end(address));
+
+    transactions
+}
+
+/// A full read at OSR 4096: the pressure result is read back and the temperature conversion
+/// started in one transaction.
+fn read_transactions(address: u8, d1: [u8; 3], d2: [u8; 3]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(address, vec![0x48]),
+        Transaction::transaction_start(address),
+        Transaction::write(address, vec![0x00]),
+        Transaction::read(address, d1.to_vec()),
+        Transaction::write(address, vec![0x58]),
+        Transaction::transaction_end(address),
+        Transaction::write_read(address, vec![0x00], d2.to_vec()),
+    ]
+}
+
+#[test]
+fn read_prom_and_read() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+    let measurement = ms5611
+        .read(OversampleRatio::Osr4096, &mut NoopDelay::new())
+        .unwrap();
+
+    assert_eq!(
+        measurement,
+        Measurement {
+            pressure_mbar_x100: 100_009,
+            temperature_c_x100: 2007,
+        }
+    );
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_prom_rejects_bad_crc() {
+    let mut words = prom_words();
+    words[7] ^= 0x1;
+
+    let mut ms5611 = Ms5611::new(Mock::new(&prom_transactions(ADDRESS, &words)), ADDRESS);
+
+    assert!(matches!(
+        ms5611.read_prom(),
+        Err(Ms5611Error::CrcMismatch { .. })
+    ));
+    assert_eq!(ms5611.calibration(), None);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn fetch_calibration_leaves_driver_state_alone() {
+    let mut ms5611 = Ms5611::new(
+        Mock::new(&prom_transactions(ADDRESS, &prom_words())),
+        ADDRESS,
+    );
+
+    let calibration = ms5611.fetch_calibration().unwrap();
+    assert_eq!(calibration.pressure_sensitivity, 40127);
+    assert_eq!(ms5611.calibration(), None);
+    assert_eq!(ms5611.prom_bytes(), None);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn verify_calibration_detects_changed_prom() {
+    let mut changed = prom_words();
+    changed[3] ^= 0x100;
+
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(prom_transactions(ADDRESS, &prom_words()));
+    expectations.extend(prom_transactions(ADDRESS, &changed));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    assert!(ms5611.verify_calibration().unwrap());
+    assert!(!ms5611.verify_calibration().unwrap());
+    assert!(ms5611.calibration().is_some());
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_prom_unchecked_reads_only_coefficients() {
+    let words = prom_words();
+    let expectations: Vec<_> = (1u8..7)
+        .map(|index| {
+            Transaction::write_read(
+                ADDRESS,
+                vec![0xA0 + index * 2],
+                words[usize::from(index)].to_be_bytes().to_vec(),
+            )
+        })
+        .collect();
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom_unchecked().unwrap();
+    assert_eq!(ms5611.calibration().unwrap().temp_coef_temp, 28312);
+
+    ms5611.release().done();
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn read_f32_matches_integer_reading() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    let (pressure_hpa, temperature_c) = ms5611
+        .read_f32(OversampleRatio::Osr4096, &mut NoopDelay::new())
+        .unwrap();
+    assert_eq!(pressure_hpa, 100_009.0 / 100.0);
+    assert_eq!(temperature_c, 2007.0 / 100.0);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_with_osr_converts_channels_separately() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x48]),
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![0x00]),
+        Transaction::read(ADDRESS, D1.to_vec()),
+        Transaction::write(ADDRESS, vec![0x50]),
+        Transaction::transaction_end(ADDRESS),
+        Transaction::write_read(ADDRESS, vec![0x00], D2.to_vec()),
+    ]);
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+    let measurement = ms5611
+        .read_with_osr(
+            OversampleRatio::Osr4096,
+            OversampleRatio::Osr256,
+            &mut NoopDelay::new(),
+        )
+        .unwrap();
+
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_without_prom() {
+    let mut ms5611 = Ms5611::new(Mock::new(&[]), ADDRESS);
+
+    assert!(matches!(
+        ms5611.read(OversampleRatio::Osr4096, &mut NoopDelay::new()),
+        Err(Ms5611Error::PromNotRead)
+    ));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_rejects_saturated_adc() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, [0xFF; 3], D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    assert!(matches!(
+        ms5611.read(OversampleRatio::Osr4096, &mut NoopDelay::new()),
+        Err(Ms5611Error::AdcSaturated)
+    ));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_reports_stuck_pressure() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.set_stuck_reading_threshold(NonZeroU32::new(2));
+    ms5611.read_prom().unwrap();
+
+    let mut delay = NoopDelay::new();
+    assert!(ms5611.read(OversampleRatio::Osr4096, &mut delay).is_ok());
+    assert!(matches!(
+        ms5611.read(OversampleRatio::Osr4096, &mut delay),
+        Err(Ms5611Error::StuckReading)
+    ));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn stuck_reading_threshold_of_one_is_disabled() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.set_stuck_reading_threshold(NonZeroU32::new(1));
+    ms5611.read_prom().unwrap();
+
+    let mut delay = NoopDelay::new();
+    assert!(ms5611.read(OversampleRatio::Osr4096, &mut delay).is_ok());
+    assert!(ms5611.read(OversampleRatio::Osr4096, &mut delay).is_ok());
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_tracks_pressure_extremes() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, [0x8A, 0x00, 0x00], D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+    assert_eq!(ms5611.min_pressure(), None);
+
+    let mut delay = NoopDelay::new();
+    let high = ms5611.read(OversampleRatio::Osr4096, &mut delay).unwrap();
+    let low = ms5611.read(OversampleRatio::Osr4096, &mut delay).unwrap();
+    assert!(low.pressure_mbar_x100 < high.pressure_mbar_x100);
+    assert_eq!(ms5611.min_pressure(), Some(low.pressure_mbar_x100));
+    assert_eq!(ms5611.max_pressure(), Some(high.pressure_mbar_x100));
+
+    ms5611.reset_extremes();
+    assert_eq!(ms5611.max_pressure(), None);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_median3_outlier_does_not_move_extremes() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.push(Transaction::write(ADDRESS, vec![0x58]));
+    expectations.push(Transaction::write_read(ADDRESS, vec![0x00], D2.to_vec()));
+    for d1 in [D1, [0x8A, 0x00, 0x00], D1] {
+        expectations.push(Transaction::write(ADDRESS, vec![0x48]));
+        expectations.push(Transaction::write_read(ADDRESS, vec![0x00], d1.to_vec()));
+    }
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    let measurement = ms5611
+        .read_median3(OversampleRatio::Osr4096, &mut NoopDelay::new())
+        .unwrap();
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(ms5611.min_pressure(), Some(100_009));
+    assert_eq!(ms5611.max_pressure(), Some(100_009));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_temperature_raw_without_prom() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0x50]),
+        Transaction::write_read(ADDRESS, vec![0x00], D2.to_vec()),
+    ];
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+
+    assert_eq!(
+        ms5611
+            .read_temperature_raw(OversampleRatio::Osr256, &mut NoopDelay::new())
+            .unwrap(),
+        8_569_150
+    );
+
+    ms5611.release().done();
+}
+
+#[test]
+fn oversample_ratio_for_rate_fits_both_conversions() {
+    let mut ms5611 = Ms5611::new(Mock::new(&[]), ADDRESS);
+
+    assert_eq!(
+        ms5611.oversample_ratio_for_rate(50),
+        OversampleRatio::Osr4096
+    );
+    assert_eq!(
+        ms5611.oversample_ratio_for_rate(100),
+        OversampleRatio::Osr2048
+    );
+    assert_eq!(
+        ms5611.oversample_ratio_for_rate(5000),
+        OversampleRatio::Osr256
+    );
+
+    ms5611.set_delay_margin_percent(20);
+    assert_eq!(
+        ms5611.oversample_ratio_for_rate(50),
+        OversampleRatio::Osr2048
+    );
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_polled_restarts_unfinished_conversions() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x48]),
+        Transaction::write_read(ADDRESS, vec![0x00], vec![0; 3]),
+        Transaction::write(ADDRESS, vec![0x48]),
+        Transaction::write_read(ADDRESS, vec![0x00], D1.to_vec()),
+        Transaction::write(ADDRESS, vec![0x58]),
+        Transaction::write_read(ADDRESS, vec![0x00], D2.to_vec()),
+    ]);
+    // The full conversion time every attempt, plus one step for the retry
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(9040),
+        DelayTransaction::delay_us(9040 + 500),
+        DelayTransaction::delay_us(9040),
+    ]);
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    assert_eq!(
+        ms5611
+            .read_polled(OversampleRatio::Osr4096, 2, 500, &mut delay)
+            .unwrap()
+            .pressure_mbar_x100,
+        100_009
+    );
+
+    ms5611.release().done();
+    delay.done();
+}
+
+#[test]
+fn try_new_resets_and_reads_prom() {
+    let mut expectations = vec![Transaction::write(ADDRESS, vec![0x1E])];
+    expectations.extend(prom_transactions(ADDRESS, &prom_words()));
+
+    // The power-on delay comes before the reset, which is followed by the PROM reload wait
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_ms(10),
+        DelayTransaction::delay_ms(3),
+    ]);
+    let ms5611 = Ms5611::try_new(Mock::new(&expectations), ADDRESS, 10, &mut delay).unwrap();
+    assert!(ms5611.calibration().is_some());
+
+    ms5611.release().done();
+    delay.done();
+}
+
+#[test]
+fn read_retries_failed_conversions() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.push(Transaction::write(ADDRESS, vec![0x48]).with_error(ErrorKind::Other));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.set_adc_retries(1);
+    ms5611.read_prom().unwrap();
+
+    assert_eq!(
+        ms5611
+            .read(OversampleRatio::Osr4096, &mut NoopDelay::new())
+            .unwrap()
+            .pressure_mbar_x100,
+        100_009
+    );
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_prom_with_retries_retries_single_words() {
+    let words = prom_words();
+    let mut expectations = Vec::new();
+    for (index, word) in (0u8..).zip(words) {
+        let command = vec![0xA0 + index * 2];
+        if index == 3 {
+            expectations.push(
+                Transaction::write_read(ADDRESS, command.clone(), vec![0; 2])
+                    .with_error(ErrorKind::Other),
+            );
+        }
+        expectations.push(Transaction::write_read(
+            ADDRESS,
+            command,
+            word.to_be_bytes().to_vec(),
+        ));
+    }
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611
+        .read_prom_with_retries(1, &mut NoopDelay::new())
+        .unwrap();
+    assert_eq!(ms5611.prom_words(), Some(words));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn read_prom_rejects_blank_prom() {
+    let mut ms5611 = Ms5611::new(
+        Mock::new(&prom_transactions(ADDRESS, &[0xFFFF; 8])),
+        ADDRESS,
+    );
+
+    assert!(matches!(
+        ms5611.read_prom(),
+        Err(Ms5611Error::InvalidCalibration)
+    ));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn two_sensors_share_a_bus() {
+    let upper = Ms5611Address::Csb0 as u8;
+    let lower = Ms5611Address::Csb1 as u8;
+
+    let mut expectations = prom_transactions(upper, &prom_words());
+    expectations.extend(prom_transactions(lower, &prom_words()));
+    expectations.extend(read_transactions(upper, D1, D2));
+    expectations.extend(read_transactions(lower, D1, D2));
+
+    let bus = RefCell::new(Mock::new(&expectations));
+    let mut delay = NoopDelay::new();
+    let mut upper = Ms5611::new(RefCellDevice::new(&bus), upper);
+    let mut lower = Ms5611::new(RefCellDevice::new(&bus), lower);
+
+    upper.read_prom().unwrap();
+    lower.read_prom().unwrap();
+    let upper_measurement = upper.read(OversampleRatio::Osr4096, &mut delay).unwrap();
+    let lower_measurement = lower.read(OversampleRatio::Osr4096, &mut delay).unwrap();
+
+    assert_eq!(upper_measurement, lower_measurement);
+    assert_eq!(upper_measurement.pressure_mbar_x100, 100_009);
+
+    upper.release();
+    lower.release();
+    bus.into_inner().done();
+}
+
+#[test]
+fn is_connected_reads_the_prom_in_one_transaction() {
+    let mut ms5611 = Ms5611::new(
+        Mock::new(&prom_transactions(ADDRESS, &prom_words())),
+        ADDRESS,
+    );
+
+    assert_eq!(ms5611.is_connected(), Ok(true));
+    assert_eq!(ms5611.calibration(), None);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn is_connected_rejects_blank_prom() {
+    let mut ms5611 = Ms5611::new(
+        Mock::new(&prom_transactions(ADDRESS, &[0xFFFF; 8])),
+        ADDRESS,
+    );
+
+    assert_eq!(ms5611.is_connected(), Ok(false));
+
+    ms5611.release().done();
+}
+
+/// I2C bus on which nothing acknowledges its address, or every transfer fails with `kind`.
+struct FailingI2c {
+    kind: ErrorKind,
+}
+
+impl embedded_hal::i2c::ErrorType for FailingI2c {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for FailingI2c {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Err(self.kind)
+    }
+}
+
+#[test]
+fn is_connected_treats_address_nak_as_absent() {
+    let kind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let mut ms5611 = Ms5611::new(FailingI2c { kind }, ADDRESS);
+
+    assert_eq!(ms5611.is_connected(), Ok(false));
+}
+
+#[test]
+fn is_connected_returns_other_bus_errors() {
+    let mut ms5611 = Ms5611::new(
+        FailingI2c {
+            kind: ErrorKind::Bus,
+        },
+        ADDRESS,
+    );
+
+    assert_eq!(ms5611.is_connected(), Err(Ms5611Error::Bus(ErrorKind::Bus)));
+}
+
+#[test]
+fn read_filtered_blends_readings() {
+    // D2 for -25.71 °C, where D1 compensates to 909.41 mbar
+    const COLD_D2: [u8; 3] = [0x70, 0xEA, 0x40];
+
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, D1, COLD_D2));
+    expectations.extend(read_transactions(ADDRESS, D1, COLD_D2));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    let mut delay = NoopDelay::new();
+    ms5611.read_prom().unwrap();
+
+    let mut read_filtered = |ms5611: &mut Ms5611<_>, alpha| {
+        let measurement = ms5611
+            .read_filtered(OversampleRatio::Osr4096, alpha, &mut delay)
+            .unwrap();
+        (
+            measurement.pressure_mbar_x100,
+            measurement.temperature_c_x100,
+        )
+    };
+
+    // The first sample initializes the average, then half of each new sample is blended in
+    assert_eq!(read_filtered(&mut ms5611, 128), (100_009, 2007));
+    assert_eq!(read_filtered(&mut ms5611, 128), (95_475, -282));
+
+    ms5611.reset_filter();
+    assert_eq!(read_filtered(&mut ms5611, 1), (90_941, -2571));
+    // 1/256 of the step lands the average at -25.531 °C, which the shift floors to -25.54
+    assert_eq!(read_filtered(&mut ms5611, 1), (90_976, -2554));
+
+    ms5611.release().done();
+}
+
+#[test]
+fn measure_noise_returns_standard_deviation() {
+    // D1 values for 999.09 and 1001.09 mbar, so every sample is 1 mbar off the mean
+    const LOW_D1: [u8; 3] = [0x8A, 0x8D, 0xE1];
+    const HIGH_D1: [u8; 3] = [0x8A, 0xB6, 0x80];
+
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    for d1 in [LOW_D1, HIGH_D1, LOW_D1, HIGH_D1] {
+        expectations.extend(read_transactions(ADDRESS, d1, D2));
+    }
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    assert_eq!(
+        ms5611.measure_noise(OversampleRatio::Osr4096, 4, &mut NoopDelay::new()),
+        Ok((100, 0))
+    );
+    assert_eq!(ms5611.min_pressure(), None);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn measure_noise_of_fewer_than_two_samples_is_zero() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+    expectations.extend(read_transactions(ADDRESS, D1, D2));
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    let mut delay = NoopDelay::new();
+    ms5611.read_prom().unwrap();
+
+    // Zero samples still takes one reading
+    for samples in [0, 1] {
+        assert_eq!(
+            ms5611.measure_noise(OversampleRatio::Osr4096, samples, &mut delay),
+            Ok((0, 0))
+        );
+    }
+
+    ms5611.release().done();
+}
+
+#[test]
+fn non_blocking_conversions() {
+    let mut expectations = prom_transactions(ADDRESS, &prom_words());
+    expectations.extend([
+        Transaction::write(ADDRESS, vec![0x58]),
+        Transaction::write_read(ADDRESS, vec![0x00], D2.to_vec()),
+        Transaction::write(ADDRESS, vec![0x48]),
+        Transaction::write_read(ADDRESS, vec![0x00], D1.to_vec()),
+    ]);
+
+    let mut ms5611 = Ms5611::new(Mock::new(&expectations), ADDRESS);
+    ms5611.read_prom().unwrap();
+
+    assert!(!ms5611.is_ready(u32::MAX));
+    assert_eq!(ms5611.read_result(), Err(Ms5611Error::NoConversionPending));
+    assert_eq!(
+        ms5611.compensate_pressure(9_085_466),
+        Err(Ms5611Error::TemperatureNotRead)
+    );
+
+    // OSR 4096 converts in up to 9.04 ms
+    ms5611
+        .start_conversion(OversampleRatio::Osr4096, ConvertChannel::Temperature)
+        .unwrap();
+    assert!(!ms5611.is_ready(9));
+    assert!(ms5611.is_ready(10));
+    assert_eq!(ms5611.read_result(), Ok(8_569_150));
+    assert!(!ms5611.is_ready(u32::MAX));
+    assert_eq!(ms5611.read_result(), Err(Ms5611Error::NoConversionPending));
+
+    // A 50% margin stretches that to 13.56 ms
+    ms5611.set_delay_margin_percent(50);
+    ms5611
+        .start_conversion(OversampleRatio::Osr4096, ConvertChannel::Pressure)
+        .unwrap();
+    assert!(!ms5611.is_ready(13));
+    assert!(ms5611.is_ready(14));
+    let d1 = ms5611.read_result().unwrap();
+
+    // The temperature result was cached for compensation
+    let measurement = ms5611.compensate_pressure(d1).unwrap();
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(measurement.temperature_c_x100, 2007);
+
+    ms5611.release().done();
+}
+
+#[test]
+fn builder_configures_read_configured() {
+    let address = Ms5611Address::Csb1 as u8;
+    let mut expectations = vec![Transaction::write(address, vec![0x1E])];
+    expectations.extend(prom_transactions(address, &prom_words()));
+    // OSR 1024 conversion commands
+    expectations.extend([
+        Transaction::write(address, vec![0x44]),
+        Transaction::transaction_start(address),
+        Transaction::write(address, vec![0x00]),
+        Transaction::read(address, D1.to_vec()),
+        Transaction::write(address, vec![0x54]),
+        Transaction::transaction_end(address),
+        Transaction::write_read(address, vec![0x00], D2.to_vec()),
+    ]);
+    expectations.extend(read_transactions(address, D1, D2));
+
+    let mut stored_delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(2280),
+        DelayTransaction::delay_us(2280),
+        DelayTransaction::delay_us(9040),
+        DelayTransaction::delay_us(9040),
+    ]);
+    let mut ms5611 = Ms5611Builder::new()
+        .address(Ms5611Address::Csb1)
+        .oversample_ratio(OversampleRatio::Osr1024)
+        .power_on_delay_ms(5)
+        .delay(&mut stored_delay)
+        .build_i2c(Mock::new(&expectations));
+    assert_eq!(ms5611.address(), address);
+
+    let mut init_delay =
+        CheckedDelay::new(&[DelayTransaction::delay_ms(5), DelayTransaction::delay_ms(3)]);
+    ms5611.init(&mut init_delay).unwrap();
+
+    let measurement = ms5611.read_configured().unwrap();
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(measurement.temperature_c_x100, 2007);
+
+    // Overriding the ratio still waits with the stored delay
+    let measurement = ms5611.read_with(OversampleRatio::Osr4096).unwrap();
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(ms5611.max_pressure(), Some(100_009));
+
+    ms5611.release().done();
+    init_delay.done();
+    stored_delay.done();
+}
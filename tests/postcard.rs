@@ -0,0 +1,21 @@
+#![cfg(feature = "serde")]
+
+use ms5611_rs::Measurement;
+
+#[test]
+fn measurement_postcard_round_trip() {
+    let measurement = Measurement {
+        pressure_mbar_x100: 100_009,
+        temperature_c_x100: 2007,
+    };
+
+    let mut buf = [0u8; 16];
+    let encoded = postcard::to_slice(&measurement, &mut buf).unwrap();
+
+    // Varint pressure followed by zigzag varint temperature
+    assert_eq!(encoded, &[0xA9, 0x8D, 0x06, 0xAE, 0x1F]);
+    assert_eq!(
+        postcard::from_bytes::<Measurement>(encoded).unwrap(),
+        measurement
+    );
+}
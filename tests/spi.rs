@@ -0,0 +1,182 @@
+#![cfg(feature = "spi")]
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{ErrorKind, ErrorType, SpiBus};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+use ms5611_rs::{verify_prom_crc, Interface, Ms5611, OversampleRatio, SpiError, SpiInterface};
+
+/// Datasheet example ADC results, D1 = 9085466 and D2 = 8569150.
+const D1: [u8; 3] = [0x8A, 0xA2, 0x1A];
+const D2: [u8; 3] = [0x82, 0xC1, 0x3E];
+
+/// Datasheet calibration coefficients, with the CRC nibble filled in.
+fn prom_words() -> [u16; 8] {
+    let mut words = [0, 40127, 36924, 23317, 23282, 33464, 28312, 0];
+    words[7] = (0..16)
+        .find(|&crc| {
+            words[7] = crc;
+            verify_prom_crc(&words)
+        })
+        .expect("one of the sixteen nibbles must match");
+
+    words
+}
+
+/// A command write framed by chip select, then the bytes clocked back for it if any.
+fn framed(command: u8, response: &[u8]) -> Vec<Transaction<u8>> {
+    let mut transactions = vec![Transaction::write_vec(vec![command])];
+    if !response.is_empty() {
+        transactions.push(Transaction::read_vec(response.to_vec()));
+    }
+    transactions.push(Transaction::flush());
+
+    transactions
+}
+
+/// Chip select goes low and back high once per framed command.
+fn cs_frames(count: usize) -> Vec<PinTransaction> {
+    (0..count)
+        .flat_map(|_| {
+            [
+                PinTransaction::set(State::Low),
+                PinTransaction::set(State::High),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn reset_prom_and_read() {
+    let mut spi_expectations = framed(0x1E, &[]);
+    for (index, word) in (0u8..).zip(prom_words()) {
+        spi_expectations.extend(framed(0xA0 + index * 2, &word.to_be_bytes()));
+    }
+    spi_expectations.extend(framed(0x48, &[]));
+    spi_expectations.extend(framed(0x00, &D1));
+    spi_expectations.extend(framed(0x58, &[]));
+    spi_expectations.extend(framed(0x00, &D2));
+
+    // Reset, eight PROM words and four frames for the read
+    let mut ms5611 = Ms5611::new_spi(
+        SpiMock::new(&spi_expectations),
+        PinMock::new(&cs_frames(1 + 8 + 4)),
+    );
+    ms5611.reset().unwrap();
+    ms5611.read_prom().unwrap();
+    let measurement = ms5611
+        .read(OversampleRatio::Osr4096, &mut NoopDelay::new())
+        .unwrap();
+
+    assert_eq!(measurement.pressure_mbar_x100, 100_009);
+    assert_eq!(measurement.temperature_c_x100, 2007);
+
+    let (mut spi, mut cs) = ms5611.release();
+    spi.done();
+    cs.done();
+}
+
+/// Bus and chip-select events in the order the driver issued them.
+type Log = RefCell<Vec<&'static str>>;
+
+/// SPI bus that records its calls and, if asked to, fails every transfer.
+struct LoggingSpi<'a> {
+    log: &'a Log,
+    fail: bool,
+}
+
+impl LoggingSpi<'_> {
+    fn record(&mut self, event: &'static str) -> Result<(), ErrorKind> {
+        self.log.borrow_mut().push(event);
+        if self.fail {
+            Err(ErrorKind::Other)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ErrorType for LoggingSpi<'_> {
+    type Error = ErrorKind;
+}
+
+impl SpiBus for LoggingSpi<'_> {
+    fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+        self.record("read")
+    }
+
+    fn write(&mut self, _words: &[u8]) -> Result<(), Self::Error> {
+        self.record("write")
+    }
+
+    fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+        self.record("transfer")
+    }
+
+    fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+        self.record("transfer")
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push("flush");
+        Ok(())
+    }
+}
+
+/// Chip select that records its edges.
+struct LoggingPin<'a> {
+    log: &'a Log,
+}
+
+impl digital::ErrorType for LoggingPin<'_> {
+    type Error = Infallible;
+}
+
+impl OutputPin for LoggingPin<'_> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push("cs low");
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push("cs high");
+        Ok(())
+    }
+}
+
+#[test]
+fn flushes_before_releasing_chip_select() {
+    let log = Log::default();
+    let spi = LoggingSpi {
+        log: &log,
+        fail: false,
+    };
+    let mut interface = SpiInterface::new(spi, LoggingPin { log: &log });
+
+    interface.command_read(0x00, &mut [0; 3]).unwrap();
+
+    assert_eq!(
+        *log.borrow(),
+        ["cs low", "write", "read", "flush", "cs high"]
+    );
+}
+
+#[test]
+fn failed_transfer_releases_chip_select() {
+    let log = Log::default();
+    let spi = LoggingSpi {
+        log: &log,
+        fail: true,
+    };
+    let mut interface = SpiInterface::new(spi, LoggingPin { log: &log });
+
+    assert_eq!(
+        interface.write_command(0x1E),
+        Err(SpiError::Spi(ErrorKind::Other))
+    );
+    assert_eq!(*log.borrow(), ["cs low", "write", "cs high"]);
+}